@@ -3,9 +3,15 @@
 use crate::commodity::Commodity;
 use crate::process::Process;
 use crate::region::RegionSelection;
-use serde::Deserialize;
+use crate::time_slice::TimeSliceID;
+use anyhow::{bail, ensure, Context, Result};
+use serde::{Deserialize, Serialize};
 use serde_string_enum::DeserializeLabeledStringEnum;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::Path;
 use std::rc::Rc;
 
 /// An agent in the simulation
@@ -33,6 +39,25 @@ pub struct Agent {
     pub objectives: Vec<AgentObjective>,
 }
 
+impl Agent {
+    /// Whether this agent may invest in the given process in the specified region and year.
+    ///
+    /// The process must be in the agent's search space, both the agent and the process must
+    /// operate in the region and the year must be within the process's year range.
+    ///
+    /// # Arguments
+    ///
+    /// * `process` - The candidate process
+    /// * `region_id` - The region in which the investment would be made
+    /// * `year` - The year in which the investment would be made
+    pub fn can_invest_in(&self, process: &Process, region_id: &str, year: u32) -> bool {
+        self.search_space.contains(&process.id)
+            && self.regions.contains(region_id)
+            && process.regions.contains(region_id)
+            && process.parameter.years.contains(&year)
+    }
+}
+
 /// Which processes apply to this agent
 #[derive(Debug, Clone, PartialEq)]
 pub enum SearchSpace {
@@ -42,6 +67,16 @@ pub enum SearchSpace {
     Some(HashSet<Rc<str>>),
 }
 
+impl SearchSpace {
+    /// Returns true if the [`SearchSpace`] includes the given process
+    pub fn contains(&self, process_id: &str) -> bool {
+        match self {
+            Self::AllProcesses => true,
+            Self::Some(process_ids) => process_ids.contains(process_id),
+        }
+    }
+}
+
 /// The decision rule for a particular objective
 #[derive(Debug, Clone, PartialEq, DeserializeLabeledStringEnum)]
 pub enum DecisionRule {
@@ -82,9 +117,21 @@ pub enum ObjectiveType {
     EquivalentAnnualCost,
 }
 
+/// A unique identifier for an asset
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct AssetID(pub u32);
+
+impl Display for AssetID {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 /// An asset controlled by an agent.
 #[derive(Clone, Debug, PartialEq)]
 pub struct Asset {
+    /// A unique identifier for the asset, assigned when it is commissioned
+    pub id: Option<AssetID>,
     /// A unique identifier for the agent
     pub agent_id: Rc<str>,
     /// The [`Process`] that this asset corresponds to
@@ -95,7 +142,1027 @@ pub struct Asset {
     pub capacity: f64,
     /// The year the asset comes online
     pub commission_year: u32,
+    /// The year the asset was mothballed (taken offline but retained), if it has been
+    pub mothball_year: Option<u32>,
+}
+
+impl Asset {
+    /// The first year in which this asset is no longer in service
+    pub fn decommission_year(&self) -> u32 {
+        self.commission_year + self.process.parameter.lifetime
+    }
+
+    /// Get the number of years this asset has left in service as of `year`.
+    ///
+    /// # Returns
+    ///
+    /// `None` if the asset has not yet been commissioned in `year`, otherwise the number of years
+    /// until [`Asset::decommission_year`] (zero if this has already passed).
+    pub fn remaining_lifetime(&self, year: u32) -> Option<u32> {
+        if self.is_future(year) {
+            return None;
+        }
+
+        Some(self.decommission_year().saturating_sub(year))
+    }
+
+    /// Whether this asset has yet to be commissioned as of `year`
+    pub fn is_future(&self, year: u32) -> bool {
+        year < self.commission_year
+    }
+
+    /// Whether this asset is in service in `year` (i.e. commissioned but not yet decommissioned)
+    pub fn is_commissioned(&self, year: u32) -> bool {
+        !self.is_future(year) && !self.is_decommissioned(year)
+    }
+
+    /// Whether this asset has reached the end of its lifetime as of `year`
+    pub fn is_decommissioned(&self, year: u32) -> bool {
+        year >= self.decommission_year()
+    }
+
+    /// Whether this asset has been mothballed
+    pub fn is_mothballed(&self) -> bool {
+        self.mothball_year.is_some()
+    }
+
+    /// Mothball this asset, taking it offline while retaining it for possible recommissioning.
+    ///
+    /// # Returns
+    ///
+    /// An error if the asset is not in service in `year` or is already mothballed.
+    pub fn mothball(&mut self, year: u32) -> Result<()> {
+        ensure!(
+            !self.is_future(year),
+            "Cannot mothball asset which has not been commissioned"
+        );
+        ensure!(
+            !self.is_decommissioned(year),
+            "Cannot mothball asset which has been decommissioned"
+        );
+        ensure!(!self.is_mothballed(), "Asset is already mothballed");
+
+        self.mothball_year = Some(year);
+
+        Ok(())
+    }
+
+    /// The maximum annual activity of this asset
+    pub fn max_activity(&self) -> f64 {
+        self.capacity * self.process.parameter.cap2act
+    }
+
+    /// Create a copy of this asset with a different capacity.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The capacity of the new asset
+    ///
+    /// # Returns
+    ///
+    /// The new asset or an error if `capacity` is not positive.
+    pub fn with_capacity(&self, capacity: f64) -> Result<Asset> {
+        ensure!(
+            capacity > 0.0,
+            "Capacity of asset must be positive (got {capacity})"
+        );
+
+        Ok(Asset {
+            capacity,
+            ..self.clone()
+        })
+    }
+
+    /// The annual fixed operating cost of this asset
+    pub fn annual_fixed_cost(&self) -> f64 {
+        self.capacity * self.process.parameter.fixed_operating_cost
+    }
+
+    /// Calculate the realised capacity factor of this asset.
+    ///
+    /// This is the total activity of the asset over the year divided by its maximum annual
+    /// activity. This is equivalent to the mean utilisation of each time slice, weighted by the
+    /// length of the time slice, but is also well defined for time slices of zero length.
+    ///
+    /// # Arguments
+    ///
+    /// * `activity_by_time_slice` - The activity of the asset in each time slice. These are
+    ///   absolute amounts of activity over the whole time slice (in the same units as
+    ///   [`Asset::max_activity`]), not rates, so they are summed without weighting by duration.
+    ///
+    /// # Returns
+    ///
+    /// The capacity factor, or zero if the asset's maximum activity is zero.
+    pub fn capacity_factor(&self, activity_by_time_slice: &HashMap<TimeSliceID, f64>) -> f64 {
+        let max_activity = self.max_activity();
+        if max_activity == 0.0 {
+            return 0.0;
+        }
+
+        let total_activity: f64 = activity_by_time_slice.values().sum();
+        total_activity / max_activity
+    }
+
+    /// Bring a mothballed asset back into service.
+    ///
+    /// # Returns
+    ///
+    /// An error if the asset is not mothballed.
+    pub fn recommission(&mut self) -> Result<()> {
+        if self.mothball_year.take().is_none() {
+            bail!("Cannot recommission asset which is not mothballed");
+        }
+
+        Ok(())
+    }
+}
+
+/// The representation of an [`Asset`] in a checkpoint file
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetCheckpoint {
+    id: Option<AssetID>,
+    agent_id: Rc<str>,
+    process_id: Rc<str>,
+    region_id: Rc<str>,
+    capacity: f64,
+    commission_year: u32,
+    mothball_year: Option<u32>,
+}
+
+impl AssetCheckpoint {
+    fn from_asset(asset: &Asset) -> Self {
+        Self {
+            id: asset.id,
+            agent_id: Rc::clone(&asset.agent_id),
+            process_id: Rc::clone(&asset.process.id),
+            region_id: Rc::clone(&asset.region_id),
+            capacity: asset.capacity,
+            commission_year: asset.commission_year,
+            mothball_year: asset.mothball_year,
+        }
+    }
+
+    fn into_asset(self, processes: &HashMap<Rc<str>, Rc<Process>>) -> Result<Asset> {
+        let process = processes
+            .get(&self.process_id)
+            .with_context(|| format!("Invalid process ID: {}", self.process_id))?;
+
+        Ok(Asset {
+            id: self.id,
+            agent_id: self.agent_id,
+            process: Rc::clone(process),
+            region_id: self.region_id,
+            capacity: self.capacity,
+            commission_year: self.commission_year,
+            mothball_year: self.mothball_year,
+        })
+    }
+}
+
+/// The representation of an [`AssetPool`] in a checkpoint file
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetPoolCheckpoint {
+    active: Vec<AssetCheckpoint>,
+    future: Vec<AssetCheckpoint>,
+    decommissioned: Vec<AssetCheckpoint>,
+    next_id: u32,
 }
 
 /// A pool of [`Asset`]s
-pub type AssetPool = Vec<Asset>;
+#[derive(Debug, PartialEq)]
+pub struct AssetPool {
+    /// Assets which have been commissioned, including those which are mothballed
+    active: Vec<Asset>,
+    /// Assets which have yet to be commissioned, sorted by commission year
+    future: Vec<Asset>,
+    /// Assets which have reached the end of their lifetime
+    decommissioned: Vec<Asset>,
+    /// The ID to assign to the next asset to be commissioned
+    next_id: u32,
+}
+
+impl AssetPool {
+    /// Create a new [`AssetPool`] in which none of the assets have yet been commissioned
+    pub fn new(mut assets: Vec<Asset>) -> Self {
+        // Sort in order of commission year
+        assets.sort_by_key(|asset| asset.commission_year);
+
+        Self {
+            active: Vec::new(),
+            future: assets,
+            decommissioned: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Commission assets whose commission year is on or before the specified year.
+    ///
+    /// Each newly commissioned asset is assigned a unique [`AssetID`].
+    ///
+    /// # Returns
+    ///
+    /// The IDs of the newly commissioned assets.
+    pub fn commission_new(&mut self, year: u32) -> Vec<AssetID> {
+        // `future` is sorted by commission year, so we can just take from the front
+        let count = self
+            .future
+            .iter()
+            .take_while(|asset| !asset.is_future(year))
+            .count();
+
+        let mut ids = Vec::with_capacity(count);
+        for mut asset in self.future.drain(0..count) {
+            let id = AssetID(self.next_id);
+            self.next_id += 1;
+            asset.id = Some(id);
+            ids.push(id);
+            self.active.push(asset);
+        }
+
+        ids
+    }
+
+    /// Decommission assets whose decommission year is on or before the specified year.
+    ///
+    /// # Returns
+    ///
+    /// The assets which were decommissioned by this call, in the order in which they were
+    /// commissioned.
+    pub fn decommission_old(&mut self, year: u32) -> &[Asset] {
+        let (old, active): (Vec<_>, Vec<_>) = self
+            .active
+            .drain(..)
+            .partition(|asset| asset.is_decommissioned(year));
+        self.active = active;
+
+        let start = self.decommissioned.len();
+        self.decommissioned.extend(old);
+        &self.decommissioned[start..]
+    }
+
+    /// Move a decommissioned asset back into the pool of active assets.
+    ///
+    /// The asset is brought back into service, so if it was mothballed when it was decommissioned,
+    /// it is no longer mothballed.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the decommissioned asset
+    /// * `year` - The current milestone year
+    ///
+    /// # Returns
+    ///
+    /// An error if there is no decommissioned asset with the given ID or if the asset has reached
+    /// the end of its lifetime by `year` (in which case it would just be decommissioned again).
+    pub fn reinstate(&mut self, id: AssetID, year: u32) -> Result<()> {
+        let idx = self
+            .decommissioned
+            .iter()
+            .position(|asset| asset.id == Some(id))
+            .with_context(|| format!("No decommissioned asset with ID {id}"))?;
+        ensure!(
+            !self.decommissioned[idx].is_decommissioned(year),
+            "Cannot reinstate asset {id} as it has reached the end of its lifetime by {year}"
+        );
+
+        let mut asset = self.decommissioned.remove(idx);
+        asset.mothball_year = None;
+        self.active.push(asset);
+
+        // Keep active assets in the order in which they were commissioned
+        self.active.sort_by_key(|asset| asset.id);
+
+        Ok(())
+    }
+
+    /// Iterate over assets which have been decommissioned
+    pub fn iter_decommissioned(&self) -> impl Iterator<Item = &Asset> {
+        self.decommissioned.iter()
+    }
+
+    /// Iterate over assets which have been commissioned and are not mothballed
+    pub fn iter_active(&self) -> impl Iterator<Item = &Asset> {
+        self.active.iter().filter(|asset| !asset.is_mothballed())
+    }
+
+    /// Iterate over assets which have been commissioned but are currently mothballed
+    pub fn iter_mothballed(&self) -> impl Iterator<Item = &Asset> {
+        self.active.iter().filter(|asset| asset.is_mothballed())
+    }
+
+    /// Get a mutable reference to the commissioned (possibly mothballed) asset with the given ID
+    fn get_commissioned_mut(&mut self, id: AssetID) -> Result<&mut Asset> {
+        self.active
+            .iter_mut()
+            .find(|asset| asset.id == Some(id))
+            .with_context(|| format!("No commissioned asset with ID {id}"))
+    }
+
+    /// Mothball a commissioned asset, taking it offline while retaining it in the pool.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the asset
+    /// * `year` - The current milestone year
+    ///
+    /// # Returns
+    ///
+    /// An error if there is no commissioned asset with the given ID or it cannot be mothballed.
+    pub fn mothball(&mut self, id: AssetID, year: u32) -> Result<()> {
+        self.get_commissioned_mut(id)?.mothball(year)
+    }
+
+    /// Bring a mothballed asset back into service.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - The ID of the asset
+    ///
+    /// # Returns
+    ///
+    /// An error if there is no commissioned asset with the given ID or it is not mothballed.
+    pub fn recommission(&mut self, id: AssetID) -> Result<()> {
+        self.get_commissioned_mut(id)?.recommission()
+    }
+
+    /// Iterate over assets which have yet to be commissioned, in order of commission year
+    pub fn iter_future(&self) -> impl Iterator<Item = &Asset> {
+        self.future.iter()
+    }
+
+    /// Get the total capacity of active assets for each process.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - If provided, only include assets in this region
+    ///
+    /// # Returns
+    ///
+    /// A map of total capacity, with the process ID as the key. Processes without any active assets
+    /// are omitted.
+    pub fn capacity_by_process(&self, region_id: Option<&str>) -> HashMap<Rc<str>, f64> {
+        let mut map = HashMap::new();
+        for asset in self
+            .iter_active()
+            .filter(|asset| region_id.is_none_or(|region_id| asset.region_id.as_ref() == region_id))
+        {
+            *map.entry(Rc::clone(&asset.process.id)).or_insert(0.0) += asset.capacity;
+        }
+
+        map
+    }
+
+    /// Get the total capacity of active assets belonging to the specified agent.
+    ///
+    /// Returns zero if the agent has no active assets.
+    pub fn total_capacity_for_agent(&self, agent_id: &str) -> f64 {
+        self.iter_active()
+            .filter(|asset| asset.agent_id.as_ref() == agent_id)
+            .map(|asset| asset.capacity)
+            .sum()
+    }
+
+    /// Save the state of this pool to a JSON file, so that a simulation can later be resumed.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the checkpoint file to create
+    pub fn save_checkpoint(&self, path: &Path) -> Result<()> {
+        let checkpoint = AssetPoolCheckpoint {
+            active: self
+                .active
+                .iter()
+                .map(AssetCheckpoint::from_asset)
+                .collect(),
+            future: self
+                .future
+                .iter()
+                .map(AssetCheckpoint::from_asset)
+                .collect(),
+            decommissioned: self
+                .decommissioned
+                .iter()
+                .map(AssetCheckpoint::from_asset)
+                .collect(),
+            next_id: self.next_id,
+        };
+
+        let file = File::create(path)
+            .with_context(|| format!("Could not create checkpoint file {}", path.display()))?;
+        serde_json::to_writer_pretty(BufWriter::new(file), &checkpoint)
+            .with_context(|| format!("Could not write checkpoint file {}", path.display()))?;
+
+        Ok(())
+    }
+
+    /// Restore an [`AssetPool`] from a JSON file created with [`AssetPool::save_checkpoint`].
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - Path to the checkpoint file
+    /// * `processes` - The model's processes
+    pub fn load_checkpoint(path: &Path, processes: &HashMap<Rc<str>, Rc<Process>>) -> Result<Self> {
+        fn read_checkpoint(
+            path: &Path,
+            processes: &HashMap<Rc<str>, Rc<Process>>,
+        ) -> Result<AssetPool> {
+            let file = File::open(path)?;
+            let checkpoint: AssetPoolCheckpoint = serde_json::from_reader(BufReader::new(file))?;
+            let into_assets = |assets: Vec<AssetCheckpoint>| -> Result<Vec<Asset>> {
+                assets
+                    .into_iter()
+                    .map(|asset| asset.into_asset(processes))
+                    .collect()
+            };
+
+            let mut future = into_assets(checkpoint.future)?;
+            future.sort_by_key(|asset| asset.commission_year);
+
+            ensure!(
+                future.iter().all(|asset| asset.id.is_none()),
+                "Assets which have not been commissioned cannot have an ID"
+            );
+
+            let active = into_assets(checkpoint.active)?;
+            let decommissioned = into_assets(checkpoint.decommissioned)?;
+
+            // Commissioned assets must have unique IDs, which newly commissioned assets won't reuse
+            let mut ids = HashSet::new();
+            for asset in active.iter().chain(decommissioned.iter()) {
+                let id = asset.id.with_context(|| {
+                    format!(
+                        "Commissioned asset for process {} has no ID",
+                        asset.process.id
+                    )
+                })?;
+                ensure!(ids.insert(id), "Duplicate asset ID: {id}");
+                ensure!(
+                    id.0 < checkpoint.next_id,
+                    "next_id must be greater than all existing asset IDs"
+                );
+            }
+
+            Ok(AssetPool {
+                active,
+                future,
+                decommissioned,
+                next_id: checkpoint.next_id,
+            })
+        }
+
+        read_checkpoint(path, processes)
+            .with_context(|| format!("Error reading checkpoint file {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{create_commodity, create_process};
+    use float_cmp::assert_approx_eq;
+    use std::fs;
+    use tempfile::tempdir;
+
+    /// Create an asset with a lifetime of 10 years
+    fn create_asset_with_commission_year(commission_year: u32) -> Asset {
+        Asset {
+            id: None,
+            agent_id: "agent1".into(),
+            process: create_process("process1"),
+            region_id: "GBR".into(),
+            capacity: 1.0,
+            commission_year,
+            mothball_year: None,
+        }
+    }
+
+    /// Create an asset commissioned in 2010 with a lifetime of 10 years
+    fn create_asset() -> Asset {
+        create_asset_with_commission_year(2010)
+    }
+
+    #[test]
+    fn test_agent_can_invest_in() {
+        let commodity = Rc::new(create_commodity("commodity1"));
+        let agent = Agent {
+            id: "agent1".into(),
+            description: "".into(),
+            commodity,
+            commodity_portion: 1.0,
+            search_space: SearchSpace::Some(["process1".into()].into_iter().collect()),
+            decision_rule: DecisionRule::Single,
+            capex_limit: None,
+            annual_cost_limit: None,
+            regions: RegionSelection::Some(["GBR".into(), "FRA".into()].into_iter().collect()),
+            objectives: Vec::new(),
+        };
+        let mut process1 = Rc::try_unwrap(create_process("process1")).unwrap();
+        process1.regions = RegionSelection::Some(["GBR".into()].into_iter().collect());
+        let process2 = create_process("process2");
+
+        // Allowed
+        assert!(agent.can_invest_in(&process1, "GBR", 2010));
+        assert!(agent.can_invest_in(&process1, "GBR", 2020));
+
+        // Outside the process's year range
+        assert!(!agent.can_invest_in(&process1, "GBR", 2009));
+        assert!(!agent.can_invest_in(&process1, "GBR", 2021));
+
+        // Agent operates in region, but process doesn't
+        assert!(!agent.can_invest_in(&process1, "FRA", 2010));
+
+        // Neither agent nor process operates in region
+        assert!(!agent.can_invest_in(&process1, "DEU", 2010));
+
+        // Process not in search space
+        assert!(!agent.can_invest_in(&process2, "GBR", 2010));
+    }
+
+    #[test]
+    fn test_asset_remaining_lifetime() {
+        let asset = create_asset();
+        assert_eq!(asset.decommission_year(), 2020);
+
+        // Not yet commissioned
+        assert_eq!(asset.remaining_lifetime(2009), None);
+
+        // Commissioned
+        assert_eq!(asset.remaining_lifetime(2010), Some(10));
+        assert_eq!(asset.remaining_lifetime(2015), Some(5));
+
+        // Decommission year reached or passed
+        assert_eq!(asset.remaining_lifetime(2020), Some(0));
+        assert_eq!(asset.remaining_lifetime(2030), Some(0));
+    }
+
+    #[test]
+    fn test_asset_state_predicates() {
+        // Commissioned in 2010 and decommissioned in 2020
+        let asset = create_asset();
+        for (year, future, commissioned, decommissioned) in [
+            (2009, true, false, false),
+            (2010, false, true, false),
+            (2019, false, true, false),
+            (2020, false, false, true),
+            (2030, false, false, true),
+        ] {
+            assert_eq!(asset.is_future(year), future);
+            assert_eq!(asset.is_commissioned(year), commissioned);
+            assert_eq!(asset.is_decommissioned(year), decommissioned);
+        }
+    }
+
+    #[test]
+    fn test_asset_with_capacity() {
+        let asset = create_asset();
+        let scaled = asset.with_capacity(2.5).unwrap();
+        assert_eq!(scaled.capacity, 2.5);
+        assert_eq!(
+            scaled,
+            Asset {
+                capacity: 2.5,
+                ..asset.clone()
+            }
+        );
+
+        // Original is unchanged
+        assert_eq!(asset.capacity, 1.0);
+
+        // Non-positive capacity
+        assert!(asset.with_capacity(0.0).is_err());
+        assert!(asset.with_capacity(-1.0).is_err());
+    }
+
+    #[test]
+    fn test_asset_annual_fixed_cost() {
+        // Fixed operating cost is 2.0 per unit capacity
+        let asset = Asset {
+            capacity: 3.0,
+            ..create_asset()
+        };
+        assert_approx_eq!(f64, asset.annual_fixed_cost(), 6.0);
+
+        let asset = Asset {
+            capacity: 0.0,
+            ..create_asset()
+        };
+        assert_eq!(asset.annual_fixed_cost(), 0.0);
+    }
+
+    #[test]
+    fn test_asset_capacity_factor() {
+        let slices = [
+            TimeSliceID {
+                season: "winter".into(),
+                time_of_day: "day".into(),
+            },
+            TimeSliceID {
+                season: "summer".into(),
+                time_of_day: "day".into(),
+            },
+        ];
+        let asset = Asset {
+            capacity: 2.0,
+            ..create_asset()
+        };
+
+        // Fully utilised
+        let activity = [(slices[0].clone(), 0.5), (slices[1].clone(), 1.5)]
+            .into_iter()
+            .collect();
+        assert_approx_eq!(f64, asset.capacity_factor(&activity), 1.0);
+
+        // Half utilised
+        let activity = [(slices[0].clone(), 0.25), (slices[1].clone(), 0.75)]
+            .into_iter()
+            .collect();
+        assert_approx_eq!(f64, asset.capacity_factor(&activity), 0.5);
+
+        // A time slice with no activity (e.g. one of zero length) should not affect the result
+        let night = TimeSliceID {
+            season: "winter".into(),
+            time_of_day: "night".into(),
+        };
+        let mut activity = activity;
+        activity.insert(night, 0.0);
+        assert_approx_eq!(f64, asset.capacity_factor(&activity), 0.5);
+
+        // Zero max activity
+        let asset = Asset {
+            capacity: 0.0,
+            ..create_asset()
+        };
+        assert_eq!(asset.capacity_factor(&activity), 0.0);
+    }
+
+    #[test]
+    fn test_asset_pool_iter_future() {
+        let assets = [2030, 2010, 2020, 2040]
+            .into_iter()
+            .map(create_asset_with_commission_year)
+            .collect();
+        let mut pool = AssetPool::new(assets);
+
+        // Nothing commissioned yet
+        assert!(pool.iter_active().next().is_none());
+        assert!(pool
+            .iter_future()
+            .map(|asset| asset.commission_year)
+            .eq([2010, 2020, 2030, 2040]));
+
+        pool.commission_new(2020);
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.commission_year)
+            .eq([2010, 2020]));
+        assert!(pool
+            .iter_future()
+            .map(|asset| asset.commission_year)
+            .eq([2030, 2040]));
+    }
+
+    #[test]
+    fn test_asset_pool_commission_new() {
+        let assets = [2015, 2020, 2025]
+            .into_iter()
+            .map(create_asset_with_commission_year)
+            .collect();
+        let mut pool = AssetPool::new(assets);
+
+        // Assets commissioned before or in the milestone year are in service; later ones are not
+        pool.commission_new(2020);
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.commission_year)
+            .eq([2015, 2020]));
+        assert!(pool
+            .iter_future()
+            .map(|asset| asset.commission_year)
+            .eq([2025]));
+
+        // Remaining asset is commissioned once its commission year is reached
+        pool.commission_new(2030);
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.commission_year)
+            .eq([2015, 2020, 2025]));
+        assert!(pool.iter_future().next().is_none());
+    }
+
+    #[test]
+    fn test_asset_pool_commission_new_ids() {
+        let assets = [2010, 2020, 2010]
+            .into_iter()
+            .map(create_asset_with_commission_year)
+            .collect();
+        let mut pool = AssetPool::new(assets);
+
+        let ids = pool.commission_new(2010);
+        assert_eq!(ids, [AssetID(0), AssetID(1)]);
+        assert!(pool.iter_active().map(|asset| asset.id.unwrap()).eq(ids));
+
+        // IDs continue to be unique in later years
+        assert_eq!(pool.commission_new(2020), [AssetID(2)]);
+        assert!(pool.commission_new(2030).is_empty());
+    }
+
+    #[test]
+    fn test_asset_pool_decommission_old() {
+        let assets = [2010, 2015, 2020]
+            .into_iter()
+            .map(create_asset_with_commission_year)
+            .collect();
+        let mut pool = AssetPool::new(assets);
+        pool.commission_new(2020);
+
+        // Assets have a lifetime of 10 years
+        assert!(pool
+            .decommission_old(2025)
+            .iter()
+            .map(|asset| asset.commission_year)
+            .eq([2010, 2015]));
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.commission_year)
+            .eq([2020]));
+        assert!(pool
+            .iter_decommissioned()
+            .map(|asset| asset.commission_year)
+            .eq([2010, 2015]));
+
+        // Only assets decommissioned by the latest call are returned
+        assert!(pool
+            .decommission_old(2030)
+            .iter()
+            .map(|asset| asset.commission_year)
+            .eq([2020]));
+        assert!(pool.decommission_old(2030).is_empty());
+        assert_eq!(pool.iter_decommissioned().count(), 3);
+    }
+
+    #[test]
+    fn test_asset_pool_reinstate() {
+        let assets = [2010, 2015, 2020]
+            .into_iter()
+            .map(create_asset_with_commission_year)
+            .collect();
+        let mut pool = AssetPool::new(assets);
+        let ids = pool.commission_new(2020);
+        pool.mothball(ids[1], 2020).unwrap();
+
+        // Decommission assets too early, by mistake
+        pool.decommission_old(2025);
+
+        // Asset is moved back to active, in commissioning order, and is no longer mothballed
+        pool.reinstate(ids[1], 2020).unwrap();
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.id.unwrap())
+            .eq([ids[1], ids[2]]));
+        assert!(pool
+            .iter_decommissioned()
+            .map(|asset| asset.id.unwrap())
+            .eq([ids[0]]));
+
+        // Reinstated asset is not decommissioned again, as it is still within its lifetime
+        assert!(pool.decommission_old(2020).is_empty());
+        assert_eq!(pool.iter_active().count(), 2);
+
+        // Asset has reached the end of its lifetime
+        assert!(pool.reinstate(ids[0], 2020).is_err());
+        assert!(pool
+            .iter_decommissioned()
+            .map(|asset| asset.id.unwrap())
+            .eq([ids[0]]));
+
+        // Asset is active, not decommissioned
+        assert!(pool.reinstate(ids[1], 2020).is_err());
+        assert!(pool.reinstate(ids[2], 2020).is_err());
+
+        // Unknown ID
+        assert!(pool.reinstate(AssetID(100), 2020).is_err());
+    }
+
+    #[test]
+    fn test_asset_pool_capacity_by_process() {
+        let process2 = create_process("process2");
+        let assets = vec![
+            create_asset(),
+            Asset {
+                capacity: 2.0,
+                ..create_asset()
+            },
+            Asset {
+                process: Rc::clone(&process2),
+                capacity: 4.0,
+                ..create_asset()
+            },
+            Asset {
+                region_id: "FRA".into(),
+                capacity: 8.0,
+                ..create_asset()
+            },
+            // Not yet commissioned, so not included
+            Asset {
+                capacity: 16.0,
+                ..create_asset_with_commission_year(2030)
+            },
+        ];
+        let mut pool = AssetPool::new(assets);
+        pool.commission_new(2020);
+
+        // All regions
+        let expected = HashMap::from_iter([("process1".into(), 11.0), ("process2".into(), 4.0)]);
+        assert_eq!(pool.capacity_by_process(None), expected);
+
+        // One region
+        let expected = HashMap::from_iter([("process1".into(), 3.0), ("process2".into(), 4.0)]);
+        assert_eq!(pool.capacity_by_process(Some("GBR")), expected);
+
+        // Region without any assets
+        assert!(pool.capacity_by_process(Some("USA")).is_empty());
+    }
+
+    #[test]
+    fn test_asset_mothball_recommission() {
+        let mut asset = create_asset();
+        assert!(!asset.is_mothballed());
+
+        asset.mothball(2015).unwrap();
+        assert!(asset.is_mothballed());
+        assert_eq!(asset.mothball_year, Some(2015));
+
+        asset.recommission().unwrap();
+        assert!(!asset.is_mothballed());
+        assert_eq!(asset.mothball_year, None);
+    }
+
+    #[test]
+    fn test_asset_mothball_not_commissioned() {
+        assert!(create_asset().mothball(2009).is_err());
+    }
+
+    #[test]
+    fn test_asset_mothball_decommissioned() {
+        // Asset is commissioned in 2010 with a lifetime of 10 years
+        let mut asset = create_asset();
+        assert!(asset.mothball(2019).is_ok());
+        assert!(create_asset().mothball(2020).is_err());
+        assert!(asset.mothball(2019).is_err()); // already mothballed
+    }
+
+    #[test]
+    fn test_asset_recommission_not_mothballed() {
+        assert_eq!(
+            create_asset().recommission().unwrap_err().to_string(),
+            "Cannot recommission asset which is not mothballed"
+        );
+    }
+
+    #[test]
+    fn test_asset_pool_mothballed() {
+        let assets = vec![
+            create_asset(),
+            Asset {
+                process: create_process("process2"),
+                ..create_asset()
+            },
+        ];
+        let mut pool = AssetPool::new(assets);
+        let ids = pool.commission_new(2010);
+
+        // Mothball one asset: it should no longer be active, but should be retained
+        pool.mothball(ids[0], 2010).unwrap();
+        assert!(pool
+            .iter_active()
+            .map(|asset| asset.process.id.as_ref())
+            .eq(["process2"]));
+        assert!(pool
+            .iter_mothballed()
+            .map(|asset| asset.process.id.as_ref())
+            .eq(["process1"]));
+
+        // Bring it back
+        pool.recommission(ids[0]).unwrap();
+        assert_eq!(pool.iter_active().count(), 2);
+        assert!(pool.iter_mothballed().next().is_none());
+
+        // Asset is not mothballed
+        assert!(pool.recommission(ids[0]).is_err());
+
+        // Unknown ID
+        assert!(pool.mothball(AssetID(100), 2010).is_err());
+        assert!(pool.recommission(AssetID(100)).is_err());
+    }
+
+    #[test]
+    fn test_asset_pool_total_capacity_for_agent() {
+        let assets = vec![
+            create_asset(),
+            Asset {
+                capacity: 2.0,
+                ..create_asset()
+            },
+            Asset {
+                agent_id: "agent2".into(),
+                capacity: 4.0,
+                ..create_asset()
+            },
+        ];
+        let mut pool = AssetPool::new(assets);
+        pool.commission_new(2010);
+
+        assert_eq!(pool.total_capacity_for_agent("agent1"), 3.0);
+        assert_eq!(pool.total_capacity_for_agent("agent2"), 4.0);
+        assert_eq!(pool.total_capacity_for_agent("agent3"), 0.0);
+    }
+
+    #[test]
+    fn test_asset_pool_checkpoint() {
+        let process1 = create_process("process1");
+        let process2 = create_process("process2");
+        let processes: HashMap<_, _> = [&process1, &process2]
+            .into_iter()
+            .map(|process| (Rc::clone(&process.id), Rc::clone(process)))
+            .collect();
+        let assets = vec![
+            Asset {
+                process: Rc::clone(&process1),
+                ..create_asset()
+            },
+            Asset {
+                process: Rc::clone(&process2),
+                capacity: 2.0,
+                ..create_asset_with_commission_year(2015)
+            },
+            Asset {
+                process: Rc::clone(&process2),
+                capacity: 4.0,
+                ..create_asset_with_commission_year(2030)
+            },
+        ];
+        let mut pool = AssetPool::new(assets);
+        assert_eq!(pool.commission_new(2020), [AssetID(0), AssetID(1)]);
+        pool.decommission_old(2020);
+        pool.mothball(AssetID(1), 2020).unwrap();
+
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("checkpoint.json");
+        pool.save_checkpoint(&file_path).unwrap();
+        let mut restored = AssetPool::load_checkpoint(&file_path, &processes).unwrap();
+        assert_eq!(restored, pool);
+
+        // Decommissioned asset is restored
+        assert!(restored
+            .iter_decommissioned()
+            .map(|asset| (asset.id, asset.capacity))
+            .eq([(Some(AssetID(0)), 1.0)]));
+
+        // Numbering carries on from where it left off
+        assert_eq!(restored.commission_new(2030), [AssetID(2)]);
+
+        // Process missing from model
+        let mut processes = processes;
+        processes.remove("process2");
+        assert!(AssetPool::load_checkpoint(&file_path, &processes).is_err());
+    }
+
+    #[test]
+    fn test_asset_pool_checkpoint_invalid_ids() {
+        let process = create_process("process1");
+        let processes = HashMap::from([(Rc::clone(&process.id), process)]);
+        let assets = [2010, 2015].map(create_asset_with_commission_year).into();
+        let mut pool = AssetPool::new(assets);
+        pool.commission_new(2020);
+        pool.decommission_old(2020);
+
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("checkpoint.json");
+        pool.save_checkpoint(&file_path).unwrap();
+        let checkpoint: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&file_path).unwrap()).unwrap();
+
+        // Write a modified copy of the checkpoint and check it fails to load with `expected_msg`
+        let check_invalid = |modify: &dyn Fn(&mut serde_json::Value), expected_msg: &str| {
+            let mut checkpoint = checkpoint.clone();
+            modify(&mut checkpoint);
+            fs::write(&file_path, checkpoint.to_string()).unwrap();
+            let err = AssetPool::load_checkpoint(&file_path, &processes).unwrap_err();
+            assert_eq!(err.root_cause().to_string(), expected_msg);
+        };
+
+        // Commissioned asset without an ID
+        check_invalid(
+            &|checkpoint| checkpoint["active"][0]["id"] = serde_json::Value::Null,
+            "Commissioned asset for process process1 has no ID",
+        );
+
+        // ID of active asset duplicates that of decommissioned asset
+        check_invalid(
+            &|checkpoint| {
+                checkpoint["active"][0]["id"] = checkpoint["decommissioned"][0]["id"].clone()
+            },
+            "Duplicate asset ID: 0",
+        );
+    }
+}
@@ -1,11 +1,19 @@
 //! The command line interface for the simulation.
+use crate::agent::AssetPool;
+use crate::log::LogFormat;
+use crate::output::{create_output_dir, get_output_dir, DataWriter};
 use crate::settings::Settings;
 use crate::{input::load_model, log};
 use ::log::info;
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use clap::{Parser, Subcommand};
 use include_dir::{include_dir, Dir};
-use std::path::PathBuf;
+use itertools::Itertools;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+
+/// The name of the log file written to the output folder.
+const LOG_FILE_NAME: &str = "run.log";
 
 /// The directory containing the example models.
 pub const EXAMPLES_DIR: Dir = include_dir!("examples");
@@ -27,6 +35,30 @@ pub enum Commands {
         #[arg(help = "Path to the model directory")]
         /// Path to the model directory.
         model_dir: PathBuf,
+        #[arg(long, help = "Folder in which to write output files")]
+        /// Folder in which to write output files.
+        output_dir: Option<PathBuf>,
+        #[arg(long, value_parser = parse_year_range, help = "Only run milestone years in this range (e.g. 2040..2050)")]
+        /// Only run the milestone years in this range.
+        years: Option<RangeInclusive<u32>>,
+        #[arg(
+            long,
+            help = "Asset pool checkpoint from which to resume the simulation"
+        )]
+        /// Asset pool checkpoint from which to resume the simulation.
+        checkpoint: Option<PathBuf>,
+        #[arg(long = "set", value_parser = parse_setting_override, help = "Override a setting (e.g. --set log_level=debug)")]
+        /// Overrides for settings, as key-value pairs.
+        overrides: Vec<(String, String)>,
+        #[arg(long, value_enum, default_value_t, help = "The format of log output")]
+        /// The format of log output.
+        log_format: LogFormat,
+    },
+    /// Validate a simulation model without running it.
+    Validate {
+        #[arg(help = "Path to the model directory")]
+        /// Path to the model directory.
+        model_dir: PathBuf,
     },
     /// Manage example models.
     Example {
@@ -43,13 +75,154 @@ pub enum ExampleSubcommands {
     List,
 }
 
+/// Parse a range of years in the form `start..end` (inclusive)
+fn parse_year_range(s: &str) -> Result<RangeInclusive<u32>> {
+    let (start, end) = s
+        .split_once("..")
+        .context("Year range must be in the form start..end")?;
+    let start = start.trim().parse().context("Invalid start year")?;
+    let end = end.trim().parse().context("Invalid end year")?;
+    ensure!(start <= end, "Start year cannot be after end year");
+
+    Ok(start..=end)
+}
+
+/// Parse a setting override in the form `key=value`
+fn parse_setting_override(s: &str) -> Result<(String, String)> {
+    let (key, value) = s
+        .split_once('=')
+        .context("Setting override must be in the form key=value")?;
+
+    Ok((key.trim().to_string(), value.trim().to_string()))
+}
+
+/// Get the milestone years which fall within the specified range.
+///
+/// # Arguments
+///
+/// * `milestone_years` - The model's milestone years
+/// * `range` - The range of years to run
+/// * `from_checkpoint` - Whether the asset pool is being restored from a checkpoint
+///
+/// # Returns
+///
+/// The milestone years within `range`, or an error if `range` is not within the model's years.
+/// As the asset pool is otherwise built from scratch, skipping the first milestone year is an
+/// error unless resuming from a checkpoint.
+fn filter_milestone_years(
+    milestone_years: &[u32],
+    range: &RangeInclusive<u32>,
+    from_checkpoint: bool,
+) -> Result<Vec<u32>> {
+    let first = *milestone_years.first().unwrap();
+    let last = *milestone_years.last().unwrap();
+    ensure!(
+        *range.start() >= first && *range.end() <= last,
+        "Requested years {}..{} are outside the model's milestone years ({first}..{last})",
+        range.start(),
+        range.end()
+    );
+
+    let years = milestone_years
+        .iter()
+        .copied()
+        .filter(|year| range.contains(year))
+        .collect_vec();
+    ensure!(
+        !years.is_empty(),
+        "No milestone years in range {}..{}",
+        range.start(),
+        range.end()
+    );
+    ensure!(
+        from_checkpoint || years[0] == first,
+        "Cannot start from milestone year {} without a checkpoint (use --checkpoint)",
+        years[0]
+    );
+
+    Ok(years)
+}
+
 /// Handle the `run` command.
-pub fn handle_run_command(model_dir: &PathBuf) -> Result<()> {
+///
+/// # Arguments
+///
+/// * `model_dir` - Path to the model directory
+/// * `output_dir` - Folder for output files. If not provided, a folder named after the model is
+///   created inside `muse2_results`.
+/// * `years` - If provided, only run the milestone years in this range
+/// * `checkpoint` - If provided, restore the asset pool from this checkpoint file
+/// * `overrides` - Settings to override, as key-value pairs
+/// * `log_format` - The format of log output
+pub fn handle_run_command(
+    model_dir: &PathBuf,
+    output_dir: Option<&Path>,
+    years: Option<RangeInclusive<u32>>,
+    checkpoint: Option<&Path>,
+    overrides: &[(String, String)],
+    log_format: LogFormat,
+) -> Result<()> {
+    let mut settings = Settings::from_path(model_dir)?;
+    for (key, value) in overrides {
+        settings.set(key, value)?;
+    }
+    let output_path = match output_dir {
+        Some(output_dir) => {
+            create_output_dir(output_dir)?;
+            output_dir.to_path_buf()
+        }
+        None => get_output_dir(model_dir)?,
+    };
+    let log_file_path = if settings.log_to_file {
+        Some(output_path.join(LOG_FILE_NAME))
+    } else {
+        None
+    };
+    log::init(
+        settings.log_level.as_deref(),
+        log_format,
+        log_file_path.as_deref(),
+    )
+    .context("Failed to initialize logging.")?;
+    let (mut model, mut assets) =
+        load_model(model_dir, &settings).context("Failed to load model.")?;
+    if let Some(checkpoint) = checkpoint {
+        assets = AssetPool::load_checkpoint(checkpoint, &model.processes)
+            .context("Failed to load checkpoint.")?;
+    }
+    if let Some(years) = years {
+        model.milestone_years =
+            filter_milestone_years(&model.milestone_years, &years, checkpoint.is_some())?;
+    }
+    info!("Model loaded successfully: {}", model.summary());
+    info!("Random seed: {}", settings.random_seed);
+    let mut writer = DataWriter::create(&output_path)?;
+    crate::simulation::run(&model, &mut assets, &mut writer)?;
+    info!("Results written to {}", output_path.display());
+    Ok(())
+}
+
+/// Handle the `validate` command.
+///
+/// Loads the model, which checks all of the input files, and prints a summary of its contents.
+pub fn handle_validate_command(model_dir: &PathBuf) -> Result<()> {
     let settings = Settings::from_path(model_dir)?;
-    log::init(settings.log_level.as_deref()).context("Failed to initialize logging.")?;
-    let (model, assets) = load_model(model_dir).context("Failed to load model.")?;
-    info!("Model loaded successfully.");
-    crate::simulation::run(&model, &assets);
+
+    // Warnings are raised while loading the model, so the logger must be set up first
+    if !log::is_logger_initialised() {
+        log::init(settings.log_level.as_deref(), LogFormat::Text, None)
+            .context("Failed to initialize logging.")?;
+    }
+    let (model, assets) = load_model(model_dir, &settings).context("Failed to load model.")?;
+    let summary = model.summary();
+    println!("Model is valid.");
+    println!("Regions: {}", summary.num_regions);
+    println!("Commodities: {}", summary.num_commodities);
+    println!("Processes: {}", summary.num_processes);
+    println!("Agents: {}", summary.num_agents);
+    println!("Assets: {}", assets.iter_future().count());
+    println!("Milestone years: {}", summary.num_milestone_years);
+    println!("Time slices: {}", summary.num_time_slices);
     Ok(())
 }
 
@@ -64,7 +237,7 @@ pub fn handle_example_list_command() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::{Path, PathBuf};
+    use tempfile::tempdir;
     /// Get the path to the example model.
     fn get_model_dir() -> PathBuf {
         Path::new(file!())
@@ -78,17 +251,82 @@ mod tests {
     /// An integration test for the `run` command.
     #[test]
     fn test_handle_run_command() {
-        handle_run_command(&get_model_dir()).unwrap();
+        let dir = tempdir().unwrap();
+        handle_run_command(
+            &get_model_dir(),
+            Some(dir.path()),
+            None,
+            None,
+            &[],
+            LogFormat::Text,
+        )
+        .unwrap();
+        for file_name in ["capacity.csv", "decommissions.csv", LOG_FILE_NAME] {
+            assert!(dir.path().join(file_name).is_file());
+        }
 
         // Second time will fail because the logging is already initialised
+        let dir = tempdir().unwrap();
         assert_eq!(
-            handle_run_command(&get_model_dir())
-                .unwrap_err()
-                .chain()
-                .next()
-                .unwrap()
-                .to_string(),
+            handle_run_command(
+                &get_model_dir(),
+                Some(dir.path()),
+                None,
+                None,
+                &[],
+                LogFormat::Text
+            )
+            .unwrap_err()
+            .chain()
+            .next()
+            .unwrap()
+            .to_string(),
             "Failed to initialize logging."
         );
     }
+
+    #[test]
+    fn test_parse_year_range() {
+        assert_eq!(parse_year_range("2040..2050").unwrap(), 2040..=2050);
+        assert!(parse_year_range("2040").is_err());
+        assert!(parse_year_range("2050..2040").is_err());
+        assert!(parse_year_range("a..2050").is_err());
+    }
+
+    #[test]
+    fn test_parse_setting_override() {
+        assert_eq!(
+            parse_setting_override("log_level=debug").unwrap(),
+            ("log_level".to_string(), "debug".to_string())
+        );
+        assert!(parse_setting_override("log_level").is_err());
+    }
+
+    #[test]
+    fn test_filter_milestone_years() {
+        let years = [2020, 2030, 2040, 2050];
+        assert_eq!(
+            filter_milestone_years(&years, &parse_year_range("2020..2040").unwrap(), false)
+                .unwrap(),
+            [2020, 2030, 2040]
+        );
+        assert_eq!(
+            filter_milestone_years(&years, &parse_year_range("2030..2050").unwrap(), true).unwrap(),
+            [2030, 2040, 2050]
+        );
+        assert_eq!(
+            filter_milestone_years(&years, &(2035..=2045), true).unwrap(),
+            [2040]
+        );
+
+        // Skipping the first milestone year requires a checkpoint
+        assert!(filter_milestone_years(&years, &(2030..=2050), false).is_err());
+
+        // Outside the model's years
+        assert!(filter_milestone_years(&years, &(2010..=2030), true).is_err());
+        assert!(filter_milestone_years(&years, &(2040..=2060), true).is_err());
+
+        // No milestone years in range
+        assert!(filter_milestone_years(&years, &(2031..=2039), true).is_err());
+    }
 }
@@ -1,9 +1,10 @@
 #![allow(missing_docs)]
 use crate::input::*;
-use crate::time_slice::{TimeSliceID, TimeSliceLevel};
+use crate::time_slice::{TimeSliceID, TimeSliceInfo, TimeSliceLevel};
+use itertools::Itertools;
 use serde::Deserialize;
 use serde_string_enum::DeserializeLabeledStringEnum;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::rc::Rc;
 
 /// A commodity within the simulation. Represents a substance (e.g. CO2) or form of energy (e.g.
@@ -19,6 +20,9 @@ pub struct Commodity {
     pub kind: CommodityType,
     /// The time slice level for commodity balance. Can be annual, seasonal or at time slice level.
     pub time_slice_level: TimeSliceLevel,
+    /// Whether the commodity can be exported from a region, in which case demand may be negative.
+    #[serde(default)]
+    pub exportable: bool,
 
     #[serde(skip)]
     pub costs: CommodityCostMap,
@@ -27,6 +31,56 @@ pub struct Commodity {
 }
 define_id_getter! {Commodity}
 
+impl Commodity {
+    /// Whether this commodity is balanced at the specified time slice level
+    pub fn is_balanced_at(&self, level: TimeSliceLevel) -> bool {
+        self.time_slice_level == level
+    }
+
+    /// Get the demand for this commodity in every time slice for the specified region and year.
+    ///
+    /// As the commodity is only balanced at its own time slice level, the demand is first summed
+    /// over each time slice selection at that level (e.g. over each season) and then shared between
+    /// the time slices within the selection in proportion to their lengths.
+    ///
+    /// # Arguments
+    ///
+    /// * `region_id` - The region of interest
+    /// * `year` - The year of interest
+    /// * `time_slice_info` - Information about time slices
+    ///
+    /// # Returns
+    ///
+    /// The demand for each time slice. Time slices in selections with no demand defined are omitted.
+    pub fn demand_for_year(
+        &self,
+        region_id: &Rc<str>,
+        year: u32,
+        time_slice_info: &TimeSliceInfo,
+    ) -> HashMap<TimeSliceID, f64> {
+        let mut demand_map = HashMap::new();
+        for selection in time_slice_info.iter_selections_at_level(self.time_slice_level) {
+            let demands = time_slice_info
+                .iter_selection(&selection)
+                .filter_map(|(time_slice, _)| {
+                    self.demand
+                        .get(Rc::clone(region_id), year, time_slice.clone())
+                })
+                .collect_vec();
+            if demands.is_empty() {
+                continue;
+            }
+
+            let total = demands.into_iter().sum();
+            for (time_slice, demand) in time_slice_info.calculate_share(&selection, total) {
+                demand_map.insert(time_slice.clone(), demand);
+            }
+        }
+
+        demand_map
+    }
+}
+
 /// Type of balance for application of cost
 #[derive(PartialEq, Clone, Debug, DeserializeLabeledStringEnum)]
 pub enum BalanceType {
@@ -51,13 +105,15 @@ pub struct CommodityCost {
 #[derive(PartialEq, Eq, Hash, Debug)]
 struct CommodityCostKey {
     region_id: Rc<str>,
-    year: u32,
     time_slice: TimeSliceID,
 }
 
 /// A data structure for easy lookup of [`CommodityCost`]s
+///
+/// Costs are grouped by region and time slice, then ordered by year, so that the nearest earlier
+/// year can be found efficiently.
 #[derive(PartialEq, Debug, Default)]
-pub struct CommodityCostMap(HashMap<CommodityCostKey, CommodityCost>);
+pub struct CommodityCostMap(HashMap<CommodityCostKey, BTreeMap<u32, CommodityCost>>);
 
 impl CommodityCostMap {
     /// Create a new, empty [`CommodityCostMap`]
@@ -75,13 +131,15 @@ impl CommodityCostMap {
     ) -> Option<CommodityCost> {
         let key = CommodityCostKey {
             region_id,
-            year,
             time_slice,
         };
-        self.0.insert(key, value)
+        self.0.entry(key).or_default().insert(year, value)
     }
 
-    /// Retrieve a [`CommodityCost`] from the map
+    /// Retrieve a [`CommodityCost`] from the map.
+    ///
+    /// If there is no cost for `year` itself, the cost for the nearest earlier year with the same
+    /// region and time slice is returned instead. `None` is returned only if there is no such year.
     pub fn get(
         &self,
         region_id: Rc<str>,
@@ -90,10 +148,13 @@ impl CommodityCostMap {
     ) -> Option<&CommodityCost> {
         let key = CommodityCostKey {
             region_id,
-            year,
             time_slice,
         };
-        self.0.get(&key)
+        self.0
+            .get(&key)?
+            .range(..=year)
+            .next_back()
+            .map(|(_, cost)| cost)
     }
 }
 
@@ -155,9 +216,37 @@ impl DemandMap {
     }
 }
 
+/// Calculate the average price of a commodity, weighted by flow.
+///
+/// For example, this can be used to calculate a representative annual price for a commodity in a
+/// region from the prices and flows in each time slice.
+///
+/// # Arguments
+///
+/// * `prices_and_flows` - Pairs of prices and the corresponding flows (e.g. one per time slice)
+///
+/// # Returns
+///
+/// `sum(price * flow) / sum(flow)`, or `None` if the total flow is zero.
+pub fn flow_weighted_average_price<I>(prices_and_flows: I) -> Option<f64>
+where
+    I: IntoIterator<Item = (f64, f64)>,
+{
+    let (total_cost, total_flow) = prices_and_flows
+        .into_iter()
+        .fold((0.0, 0.0), |(cost, flow), (price, ts_flow)| {
+            (cost + price * ts_flow, flow + ts_flow)
+        });
+
+    (total_flow != 0.0).then(|| total_cost / total_flow)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixture::create_commodity;
+    use float_cmp::assert_approx_eq;
+    use itertools::iproduct;
 
     #[test]
     fn test_demand_map() {
@@ -188,4 +277,129 @@ mod tests {
             .is_none());
         assert_eq!(map.get("GBR".into(), 2010, ts).unwrap(), &value);
     }
+
+    #[test]
+    fn test_commodity_cost_map_earlier_year_fallback() {
+        let ts = TimeSliceID {
+            season: "winter".into(),
+            time_of_day: "day".into(),
+        };
+        let cost = |value| CommodityCost {
+            balance_type: BalanceType::Net,
+            value,
+        };
+        let mut map = CommodityCostMap::new();
+        map.insert("GBR".into(), 2010, ts.clone(), cost(1.0));
+        map.insert("GBR".into(), 2020, ts.clone(), cost(2.0));
+
+        // Exact match
+        assert_eq!(map.get("GBR".into(), 2020, ts.clone()).unwrap(), &cost(2.0));
+
+        // Falls back to nearest earlier year
+        assert_eq!(map.get("GBR".into(), 2015, ts.clone()).unwrap(), &cost(1.0));
+        assert_eq!(map.get("GBR".into(), 2030, ts.clone()).unwrap(), &cost(2.0));
+
+        // No cost for this year or any earlier one
+        assert!(map.get("GBR".into(), 2005, ts.clone()).is_none());
+        assert!(map.get("FRA".into(), 2020, ts).is_none());
+    }
+
+    #[test]
+    fn test_commodity_is_balanced_at() {
+        let levels = [
+            TimeSliceLevel::Annual,
+            TimeSliceLevel::Season,
+            TimeSliceLevel::DayNight,
+        ];
+        for (commodity_level, queried_level) in iproduct!(levels, levels) {
+            let commodity = Commodity {
+                time_slice_level: commodity_level,
+                ..create_commodity("commodity1")
+            };
+            assert_eq!(
+                commodity.is_balanced_at(queried_level),
+                commodity_level == queried_level
+            );
+        }
+    }
+
+    #[test]
+    fn test_commodity_demand_for_year() {
+        let time_slices = [("winter", "day"), ("winter", "night"), ("summer", "day")].map(
+            |(season, time_of_day)| TimeSliceID {
+                season: season.into(),
+                time_of_day: time_of_day.into(),
+            },
+        );
+        let time_slice_info = TimeSliceInfo {
+            seasons: ["winter".into(), "summer".into()].into_iter().collect(),
+            times_of_day: ["day".into(), "night".into()].into_iter().collect(),
+            fractions: [
+                (time_slices[0].clone(), 0.25),
+                (time_slices[1].clone(), 0.25),
+                (time_slices[2].clone(), 0.5),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let mut demand = DemandMap::new();
+        for (time_slice, value) in time_slices.iter().zip([1.0, 3.0, 2.0]) {
+            demand.insert("GBR".into(), 2020, time_slice.clone(), value);
+            demand.insert("GBR".into(), 2030, time_slice.clone(), 10.0 * value);
+        }
+
+        // Demand is aggregated to the commodity's level, then shared according to time slice length
+        for (level, expected) in [
+            (TimeSliceLevel::Annual, [1.5, 1.5, 3.0]),
+            (TimeSliceLevel::Season, [2.0, 2.0, 2.0]),
+            (TimeSliceLevel::DayNight, [1.0, 3.0, 2.0]),
+        ] {
+            let commodity = Commodity {
+                kind: CommodityType::ServiceDemand,
+                time_slice_level: level,
+                demand: demand.clone(),
+                ..create_commodity("commodity1")
+            };
+
+            let actual = commodity.demand_for_year(&"GBR".into(), 2020, &time_slice_info);
+            assert_eq!(actual.len(), time_slices.len());
+            for (time_slice, expected) in time_slices.iter().zip(expected) {
+                assert_approx_eq!(f64, actual[time_slice], expected);
+            }
+            assert_approx_eq!(
+                f64,
+                commodity
+                    .demand_for_year(&"GBR".into(), 2030, &time_slice_info)
+                    .values()
+                    .sum::<f64>(),
+                60.0
+            );
+
+            // No demand for this region
+            assert!(commodity
+                .demand_for_year(&"FRA".into(), 2020, &time_slice_info)
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn test_flow_weighted_average_price() {
+        // (10 * 1 + 20 * 3) / (1 + 3)
+        assert_approx_eq!(
+            f64,
+            flow_weighted_average_price([(10.0, 1.0), (20.0, 3.0)]).unwrap(),
+            17.5
+        );
+
+        // Time slices without flow don't contribute
+        assert_approx_eq!(
+            f64,
+            flow_weighted_average_price([(10.0, 2.0), (100.0, 0.0)]).unwrap(),
+            10.0
+        );
+
+        // Zero total flow
+        assert!(flow_weighted_average_price([(10.0, 0.0), (20.0, 0.0)]).is_none());
+        assert!(flow_weighted_average_price([]).is_none());
+    }
 }
@@ -0,0 +1,147 @@
+//! General functions related to finance.
+use crate::year::discount_factor;
+
+/// Calculate the capital recovery factor (CRF) for a given discount rate and lifetime.
+///
+/// The CRF is the ratio of a constant annual payment to the present value of receiving that
+/// payment for `lifetime` years.
+///
+/// # Arguments
+///
+/// * `discount_rate` - The discount rate
+/// * `lifetime` - The lifetime in years
+///
+/// # Returns
+///
+/// The capital recovery factor. If the discount rate is zero, this is `1 / lifetime`.
+///
+/// # Panics
+///
+/// Panics if `lifetime` is zero or `discount_rate` is -1 or less, as the CRF is undefined for
+/// these inputs. Process parameters are validated to exclude these values when they are read.
+pub fn capital_recovery_factor(discount_rate: f64, lifetime: u32) -> f64 {
+    assert!(lifetime > 0, "Lifetime must be greater than zero");
+    assert!(
+        discount_rate > -1.0,
+        "Discount rate must be greater than -1 (got {discount_rate})"
+    );
+
+    if discount_rate == 0.0 {
+        return 1.0 / lifetime as f64;
+    }
+
+    let factor = 1.0 / discount_factor(discount_rate, lifetime);
+    discount_rate * factor / (factor - 1.0)
+}
+
+/// Calculate the annual payment required to recover a capital cost.
+///
+/// # Arguments
+///
+/// * `capital_cost` - The capital cost per unit capacity
+/// * `discount_rate` - The discount rate
+/// * `lifetime` - The lifetime in years
+///
+/// # Returns
+///
+/// The annualised capital cost per unit capacity.
+pub fn annualise_capital_cost(capital_cost: f64, discount_rate: f64, lifetime: u32) -> f64 {
+    capital_cost * capital_recovery_factor(discount_rate, lifetime)
+}
+
+/// Calculate the net present value of a series of annual cash flows.
+///
+/// # Arguments
+///
+/// * `cash_flows` - Cash flows for consecutive years
+/// * `discount_rate` - The discount rate (may be negative)
+/// * `base_year_offset` - The number of years after the base year at which the first cash flow
+///   occurs
+///
+/// # Returns
+///
+/// The sum of the cash flows, discounted back to the base year. This is zero if there are no cash
+/// flows.
+pub fn net_present_value(cash_flows: &[f64], discount_rate: f64, base_year_offset: u32) -> f64 {
+    cash_flows
+        .iter()
+        .zip(base_year_offset..)
+        .map(|(cash_flow, year)| cash_flow * discount_factor(discount_rate, year))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_capital_recovery_factor() {
+        // (0.1 * 1.1^10) / (1.1^10 - 1)
+        assert_approx_eq!(
+            f64,
+            capital_recovery_factor(0.1, 10),
+            0.16274539488,
+            epsilon = 1e-10
+        );
+
+        // With a one-year lifetime, the whole cost plus interest is recovered in one payment
+        assert_approx_eq!(f64, capital_recovery_factor(0.05, 1), 1.05);
+
+        // Zero discount rate
+        assert_approx_eq!(f64, capital_recovery_factor(0.0, 10), 0.1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Lifetime must be greater than zero")]
+    fn test_capital_recovery_factor_zero_lifetime() {
+        capital_recovery_factor(0.1, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Discount rate must be greater than -1")]
+    fn test_capital_recovery_factor_invalid_discount_rate() {
+        capital_recovery_factor(-1.0, 10);
+    }
+
+    #[test]
+    fn test_annualise_capital_cost() {
+        assert_approx_eq!(
+            f64,
+            annualise_capital_cost(1000.0, 0.1, 10),
+            162.74539488,
+            epsilon = 1e-8
+        );
+        assert_approx_eq!(f64, annualise_capital_cost(1000.0, 0.0, 20), 50.0);
+    }
+
+    #[test]
+    fn test_net_present_value() {
+        // 100 + 110 / 1.1 + 121 / 1.1^2
+        assert_approx_eq!(
+            f64,
+            net_present_value(&[100.0, 110.0, 121.0], 0.1, 0),
+            300.0,
+            epsilon = 1e-10
+        );
+
+        // Cash flows start one year after the base year
+        assert_approx_eq!(
+            f64,
+            net_present_value(&[110.0, 121.0], 0.1, 1),
+            200.0,
+            epsilon = 1e-10
+        );
+
+        // Negative discount rate
+        assert_approx_eq!(
+            f64,
+            net_present_value(&[100.0, 100.0], -0.5, 0),
+            300.0,
+            epsilon = 1e-10
+        );
+
+        // No cash flows
+        assert_eq!(net_present_value(&[], 0.1, 0), 0.0);
+    }
+}
@@ -0,0 +1,57 @@
+//! Helper functions for creating common test data.
+use crate::commodity::{Commodity, CommodityCostMap, CommodityType, DemandMap};
+use crate::process::{Process, ProcessParameter};
+use crate::region::RegionSelection;
+use crate::time_slice::TimeSliceLevel;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Create an annual, supply-equals-demand commodity with no costs or demand
+pub fn create_commodity(id: &str) -> Commodity {
+    Commodity {
+        id: id.into(),
+        description: "A commodity".into(),
+        kind: CommodityType::SupplyEqualsDemand,
+        time_slice_level: TimeSliceLevel::Annual,
+        exportable: false,
+        costs: CommodityCostMap::new(),
+        demand: DemandMap::new(),
+    }
+}
+
+/// Create a map of commodities with the given IDs, created with [`create_commodity`]
+pub fn create_commodity_map(ids: &[&str]) -> HashMap<Rc<str>, Rc<Commodity>> {
+    ids.iter()
+        .map(|id| {
+            let commodity = create_commodity(id);
+            (Rc::clone(&commodity.id), Rc::new(commodity))
+        })
+        .collect()
+}
+
+/// Create parameters for a process which is available from 2010 to 2020 with a lifetime of 10
+/// years
+pub fn create_process_parameter(process_id: &str) -> ProcessParameter {
+    ProcessParameter {
+        process_id: process_id.into(),
+        years: 2010..=2020,
+        capital_cost: 5.0,
+        fixed_operating_cost: 2.0,
+        variable_operating_cost: 1.0,
+        lifetime: 10,
+        discount_rate: 0.9,
+        cap2act: 1.0,
+    }
+}
+
+/// Create a process with a lifetime of 10 years
+pub fn create_process(id: &str) -> Rc<Process> {
+    Rc::new(Process {
+        id: id.into(),
+        description: "Description".into(),
+        availabilities: vec![],
+        flows: vec![],
+        parameter: create_process_parameter(id),
+        regions: RegionSelection::All,
+    })
+}
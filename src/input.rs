@@ -1,9 +1,13 @@
 //! Common routines for handling input data.
-use crate::agent::AssetPool;
+use crate::agent::{Agent, AssetPool};
+use crate::commodity::Commodity;
 use crate::model::{Model, ModelFile};
-use anyhow::{ensure, Context, Result};
+use crate::process::Process;
+use crate::settings::Settings;
+use anyhow::{bail, ensure, Context, Result};
 use float_cmp::approx_eq;
-use itertools::Itertools;
+use itertools::{iproduct, Itertools};
+use log::warn;
 use serde::de::{Deserialize, DeserializeOwned, Deserializer};
 use std::collections::{HashMap, HashSet};
 use std::fs;
@@ -31,8 +35,30 @@ pub use time_slice::read_time_slice_info;
 pub fn read_csv<'a, T: DeserializeOwned + 'a>(
     file_path: &'a Path,
 ) -> Result<impl Iterator<Item = T> + 'a> {
-    let vec = csv::Reader::from_path(file_path)
-        .with_context(|| input_err_msg(file_path))?
+    read_csv_with_comments(file_path, false)
+}
+
+/// Read a series of type `T`s from a CSV file, optionally skipping commented-out rows.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the CSV file
+/// * `allow_comments` - Whether to ignore rows whose first non-whitespace character is `#`
+pub fn read_csv_with_comments<'a, T: DeserializeOwned + 'a>(
+    file_path: &'a Path,
+    allow_comments: bool,
+) -> Result<impl Iterator<Item = T> + 'a> {
+    let mut contents = fs::read_to_string(file_path).with_context(|| input_err_msg(file_path))?;
+    if allow_comments {
+        // The csv crate only recognises comments where `#` is the first byte of the line, so we
+        // remove indented comments ourselves
+        contents = contents
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .join("\n");
+    }
+
+    let vec = csv::Reader::from_reader(contents.as_bytes())
         .into_deserialize()
         .process_results(|iter| iter.collect_vec())
         .with_context(|| input_err_msg(file_path))?;
@@ -40,6 +66,36 @@ pub fn read_csv<'a, T: DeserializeOwned + 'a>(
     Ok(vec.into_iter())
 }
 
+/// Read a series of type `T`s from a CSV file, along with the line number of each record.
+///
+/// # Arguments
+///
+/// * `file_path` - Path to the CSV file
+///
+/// # Returns
+///
+/// Pairs of line numbers (starting from one and including the header) and records.
+pub fn read_csv_with_line_numbers<T: DeserializeOwned>(
+    file_path: &Path,
+) -> Result<impl Iterator<Item = (u64, T)>> {
+    let mut reader = csv::Reader::from_path(file_path).with_context(|| input_err_msg(file_path))?;
+    let headers = reader
+        .headers()
+        .with_context(|| input_err_msg(file_path))?
+        .clone();
+    let vec = reader
+        .records()
+        .map(|record| -> Result<(u64, T), csv::Error> {
+            let record = record?;
+            let line = record.position().map_or(0, |pos| pos.line());
+            Ok((line, record.deserialize(Some(&headers))?))
+        })
+        .process_results(|iter| iter.collect_vec())
+        .with_context(|| input_err_msg(file_path))?;
+
+    Ok(vec.into_iter())
+}
+
 /// Parse a TOML file at the specified path.
 ///
 /// # Arguments
@@ -68,6 +124,41 @@ where
     Ok(value)
 }
 
+/// Read a proportion given either as a fraction (e.g. `0.85`) or a percentage (e.g. `85%`),
+/// checking that it is between 0 and 1.
+pub fn deserialise_percentage_nonzero<'de, D>(deserialiser: D) -> Result<f64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(serde::Deserialize)]
+    #[serde(untagged)]
+    enum FractionOrPercentage {
+        Fraction(f64),
+        Text(String),
+    }
+
+    let value = match FractionOrPercentage::deserialize(deserialiser)? {
+        FractionOrPercentage::Fraction(value) => value,
+        FractionOrPercentage::Text(text) => {
+            let text = text.trim();
+            let parsed = match text.strip_suffix('%') {
+                Some(percentage) => percentage.trim().parse().map(|value: f64| value / 100.0),
+                None => text.parse(),
+            };
+            parsed.map_err(|_| {
+                serde::de::Error::custom(format!("Invalid fraction or percentage: {text}"))
+            })?
+        }
+    };
+    if !(value > 0.0 && value <= 1.0) {
+        Err(serde::de::Error::custom(
+            "Value must be > 0 and <= 1 (or > 0% and <= 100%)",
+        ))?
+    }
+
+    Ok(value)
+}
+
 /// Format an error message to include the file path. To be used with `anyhow::Context`.
 pub fn input_err_msg<P: AsRef<Path>>(file_path: P) -> String {
     format!("Error reading {}", file_path.as_ref().to_string_lossy())
@@ -104,6 +195,33 @@ pub trait IDCollection {
     ///
     /// A copy of the `Rc<str>` in `self` or an error if not found.
     fn get_id(&self, id: &str) -> Result<Rc<str>>;
+
+    /// Get several IDs after checking that they all exist in this collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `ids` - The IDs to look up
+    ///
+    /// # Returns
+    ///
+    /// Copies of the `Rc<str>`s in `self`, in the same order as `ids`, or an error listing every ID
+    /// which was not found.
+    fn get_ids<'a, I>(&self, ids: I) -> Result<Vec<Rc<str>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        let (found, unknown): (Vec<_>, Vec<_>) = ids
+            .into_iter()
+            .map(|id| self.get_id(id).map_err(|_| id))
+            .partition_result();
+        ensure!(
+            unknown.is_empty(),
+            "Unknown IDs found: {}",
+            unknown.join(", ")
+        );
+
+        Ok(found)
+    }
 }
 
 impl IDCollection for HashSet<Rc<str>> {
@@ -125,12 +243,16 @@ where
         T: HasID + DeserializeOwned,
     {
         let mut map = HashMap::new();
-        for record in read_csv::<T>(file_path)? {
-            let id = record.get_id();
+        let mut lines = HashMap::new();
+        for (line, record) in read_csv_with_line_numbers::<T>(file_path)? {
+            let id: Rc<str> = record.get_id().into();
 
-            ensure!(!map.contains_key(id), "Duplicate ID found: {id}");
+            if let Some(first_line) = lines.get(&id) {
+                bail!("Duplicate ID found: {id} (lines {first_line} and {line})");
+            }
 
-            map.insert(id.into(), record);
+            lines.insert(Rc::clone(&id), line);
+            map.insert(id, record);
         }
         ensure!(!map.is_empty(), "CSV file is empty");
 
@@ -170,14 +292,78 @@ where
     }
 }
 
+/// Collects errors arising from validating the individual rows of an input file.
+///
+/// In fail-fast mode, the first error is returned immediately. Otherwise, errors are accumulated
+/// so that they can be reported together.
+pub struct ErrorCollector {
+    collect_all: bool,
+    errors: Vec<anyhow::Error>,
+}
+
+impl ErrorCollector {
+    /// Create a new [`ErrorCollector`].
+    ///
+    /// # Arguments
+    ///
+    /// * `collect_all` - Whether to accumulate errors rather than failing on the first one
+    pub fn new(collect_all: bool) -> Self {
+        Self {
+            collect_all,
+            errors: Vec::new(),
+        }
+    }
+
+    /// Check the result of validating a single row.
+    ///
+    /// # Returns
+    ///
+    /// The value if `result` is `Ok`. Otherwise, the error is returned in fail-fast mode, or
+    /// recorded and `None` returned when collecting all errors.
+    pub fn check<T>(&mut self, result: Result<T>) -> Result<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) if self.collect_all => {
+                self.errors.push(err);
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Report any errors which have been collected, combined into a single error
+    pub fn finish(self) -> Result<()> {
+        match self.errors.len() {
+            0 => Ok(()),
+            1 => Err(self.errors.into_iter().next().unwrap()),
+            count => {
+                let messages = self
+                    .errors
+                    .iter()
+                    .map(|err| format!("  - {err:#}"))
+                    .join("\n");
+                bail!("Found {count} errors:\n{messages}")
+            }
+        }
+    }
+}
+
 /// Check that fractions sum to (approximately) one
 pub fn check_fractions_sum_to_one<I>(fractions: I) -> Result<()>
+where
+    I: Iterator<Item = f64>,
+{
+    check_fractions_sum_to_one_with_tolerance(fractions, 1e-5)
+}
+
+/// Check that fractions sum to one, within the specified tolerance
+pub fn check_fractions_sum_to_one_with_tolerance<I>(fractions: I, tolerance: f64) -> Result<()>
 where
     I: Iterator<Item = f64>,
 {
     let sum = fractions.sum();
     ensure!(
-        approx_eq!(f64, sum, 1.0, epsilon = 1e-5),
+        approx_eq!(f64, sum, 1.0, epsilon = tolerance),
         "Sum of fractions does not equal one (actual: {})",
         sum
     );
@@ -185,16 +371,89 @@ where
     Ok(())
 }
 
+/// Find commodities which do not appear in the flows of any process.
+///
+/// # Arguments
+///
+/// * `commodities` - The model's commodities
+/// * `processes` - The model's processes
+///
+/// # Returns
+///
+/// The IDs of commodities not referenced by any process, sorted alphabetically.
+fn find_orphan_commodities(
+    commodities: &HashMap<Rc<str>, Rc<Commodity>>,
+    processes: &HashMap<Rc<str>, Rc<Process>>,
+) -> Vec<Rc<str>> {
+    let used: HashSet<&str> = processes
+        .values()
+        .flat_map(|process| process.flows.iter())
+        .map(|flow| flow.commodity.id.as_ref())
+        .collect();
+
+    commodities
+        .keys()
+        .filter(|id| !used.contains(id.as_ref()))
+        .cloned()
+        .sorted()
+        .collect()
+}
+
+/// Find agents which have no processes capable of producing their commodity.
+///
+/// Only agents responsible for a non-zero portion of their commodity are checked. A process is a
+/// possible producer if it has an output flow of the agent's commodity and the agent can invest in
+/// it in the given region and year.
+///
+/// # Arguments
+///
+/// * `agents` - The model's agents
+/// * `processes` - The model's processes
+/// * `region_ids` - All possible region IDs
+/// * `milestone_years` - The model's milestone years
+///
+/// # Returns
+///
+/// The agent ID, region ID and year for every combination with no possible producers, sorted.
+fn find_agents_without_producers(
+    agents: &HashMap<Rc<str>, Agent>,
+    processes: &HashMap<Rc<str>, Rc<Process>>,
+    region_ids: &HashSet<Rc<str>>,
+    milestone_years: &[u32],
+) -> Vec<(Rc<str>, Rc<str>, u32)> {
+    iproduct!(
+        agents
+            .values()
+            .filter(|agent| agent.commodity_portion > 0.0),
+        region_ids,
+        milestone_years.iter().copied()
+    )
+    .filter(|(agent, region_id, _)| agent.regions.contains(region_id))
+    .filter(|(agent, region_id, year)| {
+        !processes.values().any(|process| {
+            process
+                .flows
+                .iter()
+                .any(|flow| flow.is_output() && flow.commodity.id == agent.commodity.id)
+                && agent.can_invest_in(process, region_id, *year)
+        })
+    })
+    .map(|(agent, region_id, year)| (Rc::clone(&agent.id), Rc::clone(region_id), year))
+    .sorted()
+    .collect()
+}
+
 /// Read a model from the specified directory.
 ///
 /// # Arguments
 ///
 /// * `model_dir` - Folder containing model configuration files
+/// * `settings` - Program settings
 ///
 /// # Returns
 ///
 /// The static model data ([`Model`]) and an [`AssetPool`] struct or an error.
-pub fn load_model<P: AsRef<Path>>(model_dir: P) -> Result<(Model, AssetPool)> {
+pub fn load_model<P: AsRef<Path>>(model_dir: P, settings: &Settings) -> Result<(Model, AssetPool)> {
     let model_file = ModelFile::from_path(&model_dir)?;
 
     let time_slice_info = read_time_slice_info(model_dir.as_ref())?;
@@ -203,15 +462,40 @@ pub fn load_model<P: AsRef<Path>>(model_dir: P) -> Result<(Model, AssetPool)> {
     let years = &model_file.milestone_years.years;
     let year_range = *years.first().unwrap()..=*years.last().unwrap();
 
-    let commodities = read_commodities(model_dir.as_ref(), &region_ids, &time_slice_info, years)?;
+    let commodities = read_commodities(
+        model_dir.as_ref(),
+        &region_ids,
+        &time_slice_info,
+        years,
+        settings,
+    )?;
     let processes = read_processes(
         model_dir.as_ref(),
         &commodities,
         &region_ids,
         &time_slice_info,
         &year_range,
+        settings,
     )?;
+    let orphans = find_orphan_commodities(&commodities, &processes);
+    if !orphans.is_empty() {
+        warn!(
+            "The following commodities are not used by any process: {}",
+            orphans.join(", ")
+        );
+    }
+
     let agents = read_agents(model_dir.as_ref(), &commodities, &processes, &region_ids)?;
+    let missing_producers = find_agents_without_producers(&agents, &processes, &region_ids, years);
+    for (agent_id, region_id, year) in missing_producers.iter() {
+        let msg = format!(
+            "Agent {agent_id} has no processes which can produce its commodity in region \
+            {region_id} in {year}"
+        );
+        ensure!(!settings.error_on_missing_producers, msg);
+        warn!("{msg}");
+    }
+
     let agent_ids = agents.keys().cloned().collect();
     let assets = read_assets(model_dir.as_ref(), &agent_ids, &processes, &region_ids)?;
 
@@ -223,13 +507,20 @@ pub fn load_model<P: AsRef<Path>>(model_dir: P) -> Result<(Model, AssetPool)> {
         time_slice_info,
         regions,
     };
-    Ok((model, assets))
+    model.validate_time_slice_coverage()?;
+
+    Ok((model, AssetPool::new(assets)))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::de::value::{Error as ValueError, F64Deserializer};
+    use crate::agent::{DecisionRule, SearchSpace};
+    use crate::fixture::{create_commodity_map, create_process};
+    use crate::process::{FlowType, ProcessFlow};
+    use crate::region::RegionSelection;
+    use float_cmp::assert_approx_eq;
+    use serde::de::value::{Error as ValueError, F64Deserializer, StrDeserializer};
     use serde::de::IntoDeserializer;
     use serde::Deserialize;
     use std::fs::File;
@@ -278,6 +569,64 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_read_csv_with_comments() {
+        let dir = tempdir().unwrap();
+        let file_path = create_csv_file(
+            dir.path(),
+            "id,value\n# A comment\nhello,1\n  # An indented comment\n\t#world,2\n",
+        );
+        let records: Vec<Record> = read_csv_with_comments(&file_path, true).unwrap().collect();
+        assert_eq!(
+            records,
+            &[Record {
+                id: "hello".to_string(),
+                value: 1,
+            }]
+        );
+
+        // Comments are not allowed
+        assert!(read_csv_with_comments::<Record>(&file_path, false).is_err());
+    }
+
+    #[test]
+    fn test_read_csv_with_line_numbers() {
+        let dir = tempdir().unwrap();
+        let file_path = create_csv_file(dir.path(), "id,value\nhello,1\nworld,2\n");
+        let records: Vec<(u64, Record)> = read_csv_with_line_numbers(&file_path).unwrap().collect();
+        assert_eq!(
+            records,
+            &[
+                (
+                    2,
+                    Record {
+                        id: "hello".to_string(),
+                        value: 1,
+                    }
+                ),
+                (
+                    3,
+                    Record {
+                        id: "world".to_string(),
+                        value: 2,
+                    }
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_csv_id_file_duplicate() {
+        let dir = tempdir().unwrap();
+        let file_path =
+            create_csv_file(dir.path(), "id,value\nprocess1,1\nprocess2,2\nprocess1,3\n");
+        let err = read_csv_id_file::<Record>(&file_path).unwrap_err();
+        assert_eq!(
+            err.chain().last().unwrap().to_string(),
+            "Duplicate ID found: process1 (lines 2 and 4)"
+        );
+    }
+
     #[test]
     fn test_read_toml() {
         let dir = tempdir().unwrap();
@@ -324,6 +673,59 @@ mod tests {
         assert!(deserialise_f64(f64::INFINITY).is_err());
     }
 
+    #[test]
+    fn test_deserialise_percentage_nonzero() {
+        let deserialise_str = |value: &str| {
+            let deserialiser: StrDeserializer<ValueError> = value.into_deserializer();
+            deserialise_percentage_nonzero(deserialiser)
+        };
+        let deserialise_f64 = |value: f64| {
+            let deserialiser: F64Deserializer<ValueError> = value.into_deserializer();
+            deserialise_percentage_nonzero(deserialiser)
+        };
+
+        // Valid fractions
+        assert_eq!(deserialise_f64(0.85), Ok(0.85));
+        assert_eq!(deserialise_f64(1.0), Ok(1.0));
+        assert_eq!(deserialise_str("0.85"), Ok(0.85));
+
+        // Valid percentages
+        assert_approx_eq!(f64, deserialise_str("85%").unwrap(), 0.85);
+        assert_approx_eq!(f64, deserialise_str(" 100 %").unwrap(), 1.0);
+        assert_approx_eq!(f64, deserialise_str("0.5%").unwrap(), 0.005);
+
+        // Out of range
+        assert!(deserialise_f64(0.0).is_err());
+        assert!(deserialise_f64(1.5).is_err());
+        assert!(deserialise_str("0%").is_err());
+        assert!(deserialise_str("150%").is_err());
+        assert!(deserialise_str("-5%").is_err());
+
+        // Not a number
+        assert!(deserialise_str("abc").is_err());
+        assert!(deserialise_str("%").is_err());
+    }
+
+    #[test]
+    fn test_get_ids() {
+        let ids: HashSet<Rc<str>> = ["A".into(), "B".into(), "C".into()].into_iter().collect();
+
+        // All valid
+        assert!(ids
+            .get_ids(["C", "A"])
+            .unwrap()
+            .iter()
+            .map(|id| id.as_ref())
+            .eq(["C", "A"]));
+        assert!(ids.get_ids([]).unwrap().is_empty());
+
+        // Partially invalid: every unknown ID is reported
+        assert_eq!(
+            ids.get_ids(["A", "X", "B", "Y"]).unwrap_err().to_string(),
+            "Unknown IDs found: X, Y"
+        );
+    }
+
     #[test]
     fn test_check_fractions_sum_to_one() {
         // Single input, valid
@@ -342,4 +744,77 @@ mod tests {
         assert!(check_fractions_sum_to_one([f64::INFINITY].into_iter()).is_err());
         assert!(check_fractions_sum_to_one([f64::NAN].into_iter()).is_err());
     }
+
+    /// Create a single process which produces `commodity1`
+    fn create_processes(
+        commodities: &HashMap<Rc<str>, Rc<Commodity>>,
+    ) -> HashMap<Rc<str>, Rc<Process>> {
+        let mut process = Rc::try_unwrap(create_process("process1")).unwrap();
+        process.flows.push(ProcessFlow {
+            process_id: "process1".into(),
+            commodity: Rc::clone(&commodities["commodity1"]),
+            flow: 1.0,
+            flow_type: FlowType::Fixed,
+            flow_cost: 0.0,
+            is_pac: true,
+        });
+        [(Rc::clone(&process.id), Rc::new(process))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn test_find_orphan_commodities() {
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
+        let processes = create_processes(&commodities);
+
+        assert_eq!(
+            find_orphan_commodities(&commodities, &processes),
+            ["commodity2".into()]
+        );
+    }
+
+    #[test]
+    fn test_find_agents_without_producers() {
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
+        let processes = create_processes(&commodities);
+
+        // agent1 is well configured, but nothing produces commodity2 for agent2
+        let agents = [("agent1", "commodity1"), ("agent2", "commodity2")]
+            .into_iter()
+            .map(|(id, commodity_id)| {
+                let agent = Agent {
+                    id: id.into(),
+                    description: "".into(),
+                    commodity: Rc::clone(&commodities[commodity_id]),
+                    commodity_portion: 1.0,
+                    search_space: SearchSpace::AllProcesses,
+                    decision_rule: DecisionRule::Single,
+                    capex_limit: None,
+                    annual_cost_limit: None,
+                    regions: RegionSelection::All,
+                    objectives: Vec::new(),
+                };
+                (Rc::clone(&agent.id), agent)
+            })
+            .collect();
+        let region_ids = ["GBR".into()].into_iter().collect();
+
+        assert_eq!(
+            find_agents_without_producers(&agents, &processes, &region_ids, &[2010, 2020]),
+            [
+                ("agent2".into(), "GBR".into(), 2010),
+                ("agent2".into(), "GBR".into(), 2020)
+            ]
+        );
+
+        // process1 is no longer available, so agent1 has no producers either
+        assert_eq!(
+            find_agents_without_producers(&agents, &processes, &region_ids, &[2030]),
+            [
+                ("agent1".into(), "GBR".into(), 2030),
+                ("agent2".into(), "GBR".into(), 2030)
+            ]
+        );
+    }
 }
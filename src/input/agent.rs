@@ -27,7 +27,7 @@ struct AgentRaw {
     /// The commodity that the agent produces (could be a service demand too).
     commodity_id: String,
     /// The proportion of the commodity production that the agent is responsible for.
-    #[serde(deserialize_with = "deserialise_proportion_nonzero")]
+    #[serde(deserialize_with = "deserialise_percentage_nonzero")]
     commodity_portion: f64,
     /// The processes that the agent will consider investing in. Expressed as process IDs separated
     /// by semicolons or `None`, meaning all processes.
@@ -114,12 +114,8 @@ where
         let search_space = match agent_raw.search_space {
             None => SearchSpace::AllProcesses,
             Some(processes) => {
-                let mut set = HashSet::new();
-                for id in processes.split(';') {
-                    set.insert(process_ids.get_id(id)?);
-                }
-
-                SearchSpace::Some(set)
+                let ids = process_ids.get_ids(processes.split(';'))?;
+                SearchSpace::Some(ids.into_iter().collect())
             }
         };
 
@@ -149,22 +145,14 @@ where
 mod tests {
     use super::*;
     use crate::agent::DecisionRule;
-    use crate::commodity::{CommodityCostMap, CommodityType, DemandMap};
+    use crate::fixture::create_commodity;
     use crate::region::RegionSelection;
-    use crate::time_slice::TimeSliceLevel;
     use std::iter;
 
     #[test]
     fn test_read_agents_file_from_iter() {
         let process_ids = ["A".into(), "B".into(), "C".into()].into_iter().collect();
-        let commodity = Rc::new(Commodity {
-            id: "commodity1".into(),
-            description: "A commodity".into(),
-            kind: CommodityType::SupplyEqualsDemand,
-            time_slice_level: TimeSliceLevel::Annual,
-            costs: CommodityCostMap::new(),
-            demand: DemandMap::new(),
-        });
+        let commodity = Rc::new(create_commodity("commodity1"));
         let commodities = iter::once(("commodity1".into(), Rc::clone(&commodity))).collect();
 
         // Valid case
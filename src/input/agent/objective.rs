@@ -109,9 +109,8 @@ fn check_objective_parameter(
 mod tests {
     use super::*;
     use crate::agent::{ObjectiveType, SearchSpace};
-    use crate::commodity::{Commodity, CommodityCostMap, CommodityType, DemandMap};
+    use crate::fixture::create_commodity;
     use crate::region::RegionSelection;
-    use crate::time_slice::TimeSliceLevel;
 
     #[test]
     fn test_check_objective_parameter() {
@@ -156,14 +155,7 @@ mod tests {
 
     #[test]
     fn test_read_agent_objectives_from_iter() {
-        let commodity = Rc::new(Commodity {
-            id: "commodity1".into(),
-            description: "A commodity".into(),
-            kind: CommodityType::SupplyEqualsDemand,
-            time_slice_level: TimeSliceLevel::Annual,
-            costs: CommodityCostMap::new(),
-            demand: DemandMap::new(),
-        });
+        let commodity = Rc::new(create_commodity("commodity1"));
         let agents: HashMap<_, _> = [(
             "agent".into(),
             Agent {
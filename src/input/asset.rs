@@ -79,11 +79,13 @@ where
         );
 
         Ok(Asset {
+            id: None,
             agent_id,
             process: Rc::clone(process),
             region_id,
             capacity: asset.capacity,
             commission_year: asset.commission_year,
+            mothball_year: None,
         })
     })
     .try_collect()
@@ -92,6 +94,7 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::fixture::create_process_parameter;
     use crate::process::ProcessParameter;
     use crate::region::RegionSelection;
     use itertools::assert_equal;
@@ -100,14 +103,8 @@ mod tests {
     #[test]
     fn test_read_assets_from_iter() {
         let process_param = ProcessParameter {
-            process_id: "process1".into(),
-            years: 2010..=2020,
-            capital_cost: 5.0,
-            fixed_operating_cost: 2.0,
-            variable_operating_cost: 1.0,
             lifetime: 5,
-            discount_rate: 0.9,
-            cap2act: 1.0,
+            ..create_process_parameter("process1")
         };
         let process = Rc::new(Process {
             id: "process1".into(),
@@ -132,11 +129,13 @@ mod tests {
             commission_year: 2010,
         };
         let asset_out = Asset {
+            id: None,
             agent_id: "agent1".into(),
             process: Rc::clone(&process),
             region_id: "GBR".into(),
             capacity: 1.0,
             commission_year: 2010,
+            mothball_year: None,
         };
         assert_equal(
             read_assets_from_iter([asset_in].into_iter(), &agent_ids, &processes, &region_ids)
@@ -1,6 +1,7 @@
 //! Code for reading in commodity-related data from CSV files.
 use crate::commodity::Commodity;
 use crate::input::*;
+use crate::settings::Settings;
 use crate::time_slice::TimeSliceInfo;
 use anyhow::Result;
 use std::collections::{HashMap, HashSet};
@@ -23,6 +24,7 @@ const COMMODITY_FILE_NAME: &str = "commodities.csv";
 /// * `region_ids` - All possible region IDs
 /// * `time_slice_info` - Information about time slices
 /// * `milestone_years` - All milestone years
+/// * `settings` - Program settings
 ///
 /// # Returns
 ///
@@ -32,23 +34,32 @@ pub fn read_commodities(
     region_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
     milestone_years: &[u32],
+    settings: &Settings,
 ) -> Result<HashMap<Rc<str>, Rc<Commodity>>> {
     let commodities = read_csv_id_file::<Commodity>(&model_dir.join(COMMODITY_FILE_NAME))?;
     let commodity_ids = commodities.keys().cloned().collect();
+    let exportable_ids = commodities
+        .values()
+        .filter(|commodity| commodity.exportable)
+        .map(|commodity| Rc::clone(&commodity.id))
+        .collect();
     let mut costs = read_commodity_costs(
         model_dir,
         &commodity_ids,
         region_ids,
         time_slice_info,
         milestone_years,
+        settings.strict_levies,
     )?;
 
     let mut demand = read_demand(
         model_dir,
         &commodity_ids,
+        &exportable_ids,
         region_ids,
         time_slice_info,
         milestone_years,
+        settings,
     )?;
 
     // Populate Vecs for each Commodity
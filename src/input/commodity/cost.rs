@@ -36,6 +36,8 @@ struct CommodityCostRaw {
 /// * `region_ids` - All possible region IDs
 /// * `time_slice_info` - Information about time slices
 /// * `milestone_years` - All milestone years
+/// * `strict` - Whether it is an error for a commodity to have costs for some but not all
+///   milestone years in a region
 ///
 /// # Returns
 ///
@@ -46,6 +48,7 @@ pub fn read_commodity_costs(
     region_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
     milestone_years: &[u32],
+    strict: bool,
 ) -> Result<HashMap<Rc<str>, CommodityCostMap>> {
     let file_path = model_dir.join(COMMODITY_COSTS_FILE_NAME);
     let commodity_costs_csv = read_csv::<CommodityCostRaw>(&file_path)?;
@@ -55,6 +58,7 @@ pub fn read_commodity_costs(
         region_ids,
         time_slice_info,
         milestone_years,
+        strict,
     )
     .with_context(|| input_err_msg(&file_path))
 }
@@ -65,15 +69,16 @@ fn read_commodity_costs_iter<I>(
     region_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
     milestone_years: &[u32],
+    strict: bool,
 ) -> Result<HashMap<Rc<str>, CommodityCostMap>>
 where
     I: Iterator<Item = CommodityCostRaw>,
 {
     let mut map = HashMap::new();
 
-    // Keep track of milestone years used for each commodity + region combo. If a user provides an
-    // entry with a given commodity + region combo for one milestone year, they must also provide
-    // entries for all the other milestone years.
+    // Keep track of milestone years used for each commodity + region combo. In strict mode, if a
+    // user provides an entry with a given commodity + region combo for one milestone year, they
+    // must also provide entries for all the other milestone years.
     let mut used_milestone_years = HashMap::new();
 
     for cost in iter {
@@ -117,6 +122,10 @@ where
             .insert(cost.year);
     }
 
+    if !strict {
+        return Ok(map);
+    }
+
     let milestone_years = HashSet::from_iter(milestone_years.iter().cloned());
     for ((commodity_id, region_id), years) in used_milestone_years.iter() {
         ensure!(
@@ -198,6 +207,7 @@ mod tests {
                 &region_ids,
                 &time_slice_info,
                 &milestone_years,
+                true,
             )
             .unwrap(),
             expected
@@ -218,6 +228,7 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
 
@@ -236,6 +247,7 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
 
@@ -254,6 +266,7 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
 
@@ -272,6 +285,7 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
 
@@ -290,6 +304,7 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
 
@@ -309,7 +324,30 @@ mod tests {
             &region_ids,
             &time_slice_info,
             &milestone_years,
+            true,
         )
         .is_err());
+
+        // Valid in lenient mode: missing milestone years are allowed
+        let cost = CommodityCostRaw {
+            commodity_id: "commodity".into(),
+            region_id: "GBR".into(),
+            balance_type: BalanceType::Consumption,
+            year: 2010,
+            time_slice: "winter.day".into(),
+            value: 0.5,
+        };
+        let costs = read_commodity_costs_iter(
+            iter::once(cost),
+            &commodity_ids,
+            &region_ids,
+            &time_slice_info,
+            &milestone_years,
+            false,
+        )
+        .unwrap();
+        assert!(costs["commodity"]
+            .get("GBR".into(), 2010, time_slice.clone())
+            .is_some());
     }
 }
@@ -3,6 +3,7 @@
 use super::demand_slicing::{read_demand_slices, DemandSliceMap, DemandSliceMapKey};
 use crate::commodity::DemandMap;
 use crate::input::*;
+use crate::settings::Settings;
 use crate::time_slice::TimeSliceInfo;
 use anyhow::{ensure, Result};
 use serde::Deserialize;
@@ -11,6 +12,7 @@ use std::path::Path;
 use std::rc::Rc;
 
 const DEMAND_FILE_NAME: &str = "demand.csv";
+const DEMAND_GROWTH_FILE_NAME: &str = "demand_growth.csv";
 
 /// Represents a single demand entry in the dataset.
 #[derive(Debug, Clone, Deserialize, PartialEq)]
@@ -25,6 +27,21 @@ struct Demand {
     demand: f64,
 }
 
+/// Represents demand given as a base-year value and an annual growth rate
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+struct DemandGrowth {
+    /// The commodity this demand entry refers to
+    commodity_id: String,
+    /// The region of the demand entry
+    region_id: String,
+    /// The year in which demand is equal to `base_demand`
+    base_year: u32,
+    /// Annual demand quantity in the base year
+    base_demand: f64,
+    /// The fractional change in demand per year (e.g. 0.02 for 2% growth)
+    annual_growth: f64,
+}
+
 /// A map relating commodity, region and year to annual demand
 pub type AnnualDemandMap = HashMap<AnnualDemandMapKey, f64>;
 
@@ -48,9 +65,11 @@ pub type CommodityRegionPairs = HashSet<(Rc<str>, Rc<str>)>;
 ///
 /// * `model_dir` - Folder containing model configuration files
 /// * `commodity_ids` - All possible IDs of commodities
+/// * `exportable_ids` - IDs of commodities for which demand may be negative
 /// * `region_ids` - All possible IDs for regions
 /// * `time_slice_info` - Information about seasons and times of day
 /// * `milestone_years` - All milestone years
+/// * `settings` - Program settings
 ///
 /// # Returns
 ///
@@ -58,18 +77,37 @@ pub type CommodityRegionPairs = HashSet<(Rc<str>, Rc<str>)>;
 pub fn read_demand(
     model_dir: &Path,
     commodity_ids: &HashSet<Rc<str>>,
+    exportable_ids: &HashSet<Rc<str>>,
     region_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
     milestone_years: &[u32],
+    settings: &Settings,
 ) -> Result<HashMap<Rc<str>, DemandMap>> {
-    let (demand, commodity_regions) =
-        read_demand_file(model_dir, commodity_ids, region_ids, milestone_years)?;
+    let (mut demand, mut commodity_regions) = read_demand_file(
+        model_dir,
+        commodity_ids,
+        exportable_ids,
+        region_ids,
+        milestone_years,
+        settings,
+    )?;
+    let (growth_demand, growth_commodity_regions) = read_demand_growth_file(
+        model_dir,
+        commodity_ids,
+        region_ids,
+        milestone_years,
+        &commodity_regions,
+        settings,
+    )?;
+    demand.extend(growth_demand);
+    commodity_regions.extend(growth_commodity_regions);
     let slices = read_demand_slices(
         model_dir,
         commodity_ids,
         region_ids,
         &commodity_regions,
         time_slice_info,
+        settings.demand_slicing_tolerance,
     )?;
 
     Ok(compute_demand_maps(&demand, &slices, time_slice_info))
@@ -81,8 +119,10 @@ pub fn read_demand(
 ///
 /// * `model_dir` - Folder containing model configuration files
 /// * `commodity_ids` - All possible IDs of commodities
+/// * `exportable_ids` - IDs of commodities for which demand may be negative
 /// * `region_ids` - All possible IDs for regions
 /// * `milestone_years` - All milestone years
+/// * `settings` - Program settings
 ///
 /// # Returns
 ///
@@ -90,12 +130,22 @@ pub fn read_demand(
 fn read_demand_file(
     model_dir: &Path,
     commodity_ids: &HashSet<Rc<str>>,
+    exportable_ids: &HashSet<Rc<str>>,
     region_ids: &HashSet<Rc<str>>,
     milestone_years: &[u32],
+    settings: &Settings,
 ) -> Result<(AnnualDemandMap, CommodityRegionPairs)> {
     let file_path = model_dir.join(DEMAND_FILE_NAME);
-    let iter = read_csv(&file_path)?;
-    read_demand_from_iter(iter, commodity_ids, region_ids, milestone_years)
+    let iter = read_csv_with_comments(&file_path, settings.allow_csv_comments)?;
+    read_demand_from_iter(
+        iter,
+        commodity_ids,
+        exportable_ids,
+        region_ids,
+        milestone_years,
+        settings.interpolate_demand,
+        settings.collect_all_errors,
+    )
 }
 
 /// Read the demand data from an iterator.
@@ -104,18 +154,26 @@ fn read_demand_file(
 ///
 /// * `iter` - An iterator of [`Demand`]s
 /// * `commodity_ids` - All possible IDs of commodities
+/// * `exportable_ids` - IDs of commodities for which demand may be negative
 /// * `region_ids` - All possible IDs for regions
 /// * `milestone_years` - All milestone years
+/// * `interpolate` - Whether to interpolate demand onto milestone years. If this is false, demand
+///   may only be given for milestone years.
+/// * `collect_all_errors` - Whether to report all invalid rows together rather than failing on the
+///   first one
 ///
 /// # Returns
 ///
-/// The demand for each combination of commodity, region and year along with a [`HashSet`] of all
-/// commodity + region pairs included in the file.
+/// The demand for each combination of commodity, region and milestone year along with a
+/// [`HashSet`] of all commodity + region pairs included in the file.
 fn read_demand_from_iter<I>(
     iter: I,
     commodity_ids: &HashSet<Rc<str>>,
+    exportable_ids: &HashSet<Rc<str>>,
     region_ids: &HashSet<Rc<str>>,
     milestone_years: &[u32],
+    interpolate: bool,
+    collect_all_errors: bool,
 ) -> Result<(AnnualDemandMap, CommodityRegionPairs)>
 where
     I: Iterator<Item = Demand>,
@@ -126,36 +184,55 @@ where
     // covered
     let mut commodity_regions = HashSet::new();
 
+    let mut errors = ErrorCollector::new(collect_all_errors);
     for demand in iter {
-        let commodity_id = commodity_ids.get_id(&demand.commodity_id)?;
-        let region_id = region_ids.get_id(&demand.region_id)?;
-
-        ensure!(
-            milestone_years.binary_search(&demand.year).is_ok(),
-            "Year {} is not a milestone year. \
-            Input of non-milestone years is currently not supported.",
-            demand.year
-        );
+        let mut read_row = || -> Result<()> {
+            let commodity_id = commodity_ids.get_id(&demand.commodity_id)?;
+            let region_id = region_ids.get_id(&demand.region_id)?;
 
-        ensure!(
-            demand.demand.is_normal() && demand.demand > 0.0,
-            "Demand must be a valid number greater than zero"
-        );
+            ensure!(
+                interpolate || milestone_years.binary_search(&demand.year).is_ok(),
+                "Year {} is not a milestone year. \
+                Input of non-milestone years is only supported if interpolate_demand is enabled.",
+                demand.year
+            );
 
-        let key = AnnualDemandMapKey {
-            commodity_id: Rc::clone(&commodity_id),
-            region_id: Rc::clone(&region_id),
-            year: demand.year,
+            // Exported commodities are represented as negative demand
+            if exportable_ids.contains(&commodity_id) {
+                ensure!(
+                    demand.demand.is_normal(),
+                    "Demand for exportable commodity {commodity_id} must be a valid non-zero number"
+                );
+            } else {
+                ensure!(
+                    demand.demand.is_normal() && demand.demand > 0.0,
+                    "Demand must be a valid number greater than zero"
+                );
+            }
+
+            let key = AnnualDemandMapKey {
+                commodity_id: Rc::clone(&commodity_id),
+                region_id: Rc::clone(&region_id),
+                year: demand.year,
+            };
+            ensure!(
+                map.insert(key, demand.demand).is_none(),
+                "Duplicate demand entries (commodity: {}, region: {}, year: {})",
+                commodity_id,
+                region_id,
+                demand.year
+            );
+
+            commodity_regions.insert((commodity_id, region_id));
+
+            Ok(())
         };
-        ensure!(
-            map.insert(key, demand.demand).is_none(),
-            "Duplicate demand entries (commodity: {}, region: {}, year: {})",
-            commodity_id,
-            region_id,
-            demand.year
-        );
+        errors.check(read_row())?;
+    }
+    errors.finish()?;
 
-        commodity_regions.insert((commodity_id, region_id));
+    if interpolate {
+        map = interpolate_demand_to_milestone_years(&map, milestone_years);
     }
 
     // If a commodity + region combination is represented, it must include entries for every
@@ -177,6 +254,181 @@ where
     Ok((map, commodity_regions))
 }
 
+/// Read the optional demand_growth.csv file.
+///
+/// # Arguments
+///
+/// * `model_dir` - Folder containing model configuration files
+/// * `commodity_ids` - All possible IDs of commodities
+/// * `region_ids` - All possible IDs for regions
+/// * `milestone_years` - All milestone years
+/// * `demand_commodity_regions` - Commodity + region pairs given in demand.csv
+/// * `settings` - Program settings
+///
+/// # Returns
+///
+/// Annual demand data, grouped by commodity, region and milestone year. This is empty if the file
+/// is not present.
+fn read_demand_growth_file(
+    model_dir: &Path,
+    commodity_ids: &HashSet<Rc<str>>,
+    region_ids: &HashSet<Rc<str>>,
+    milestone_years: &[u32],
+    demand_commodity_regions: &CommodityRegionPairs,
+    settings: &Settings,
+) -> Result<(AnnualDemandMap, CommodityRegionPairs)> {
+    let file_path = model_dir.join(DEMAND_GROWTH_FILE_NAME);
+    if !file_path.is_file() {
+        return Ok((AnnualDemandMap::new(), CommodityRegionPairs::new()));
+    }
+
+    let iter = read_csv_with_comments(&file_path, settings.allow_csv_comments)?;
+    read_demand_growth_from_iter(
+        iter,
+        commodity_ids,
+        region_ids,
+        milestone_years,
+        demand_commodity_regions,
+        settings.collect_all_errors,
+    )
+    .with_context(|| input_err_msg(&file_path))
+}
+
+/// Read demand growth data from an iterator, computing demand for each milestone year.
+///
+/// Demand for each milestone year is calculated by compounding the annual growth rate from the
+/// base year. A commodity may have demand specified either in demand.csv or as a growth rate, but
+/// not both.
+///
+/// # Arguments
+///
+/// * `iter` - An iterator of [`DemandGrowth`]s
+/// * `commodity_ids` - All possible IDs of commodities
+/// * `region_ids` - All possible IDs for regions
+/// * `milestone_years` - All milestone years
+/// * `demand_commodity_regions` - Commodity + region pairs given in demand.csv
+/// * `collect_all_errors` - Whether to report all invalid rows together rather than failing on the
+///   first one
+///
+/// # Returns
+///
+/// The demand for each combination of commodity, region and milestone year along with a
+/// [`HashSet`] of all commodity + region pairs included in the file.
+fn read_demand_growth_from_iter<I>(
+    iter: I,
+    commodity_ids: &HashSet<Rc<str>>,
+    region_ids: &HashSet<Rc<str>>,
+    milestone_years: &[u32],
+    demand_commodity_regions: &CommodityRegionPairs,
+    collect_all_errors: bool,
+) -> Result<(AnnualDemandMap, CommodityRegionPairs)>
+where
+    I: Iterator<Item = DemandGrowth>,
+{
+    let mut map = AnnualDemandMap::new();
+    let mut commodity_regions = HashSet::new();
+    let mut errors = ErrorCollector::new(collect_all_errors);
+    for growth in iter {
+        let mut read_row = || -> Result<()> {
+            let commodity_id = commodity_ids.get_id(&growth.commodity_id)?;
+            let region_id = region_ids.get_id(&growth.region_id)?;
+
+            ensure!(
+                !demand_commodity_regions
+                    .iter()
+                    .any(|(other_id, _)| *other_id == commodity_id),
+                "Demand for commodity {commodity_id} is given in both {DEMAND_FILE_NAME} and \
+                {DEMAND_GROWTH_FILE_NAME}"
+            );
+            ensure!(
+                growth.base_demand.is_normal() && growth.base_demand > 0.0,
+                "Base demand must be a valid number greater than zero"
+            );
+            ensure!(
+                growth.annual_growth.is_finite() && growth.annual_growth > -1.0,
+                "Annual growth must be a valid number greater than -1"
+            );
+            ensure!(
+                commodity_regions.insert((Rc::clone(&commodity_id), Rc::clone(&region_id))),
+                "Duplicate demand growth entries (commodity: {commodity_id}, region: {region_id})"
+            );
+
+            for year in milestone_years.iter().copied() {
+                let years_elapsed = year as i32 - growth.base_year as i32;
+                let demand = growth.base_demand * (1.0 + growth.annual_growth).powi(years_elapsed);
+                let key = AnnualDemandMapKey {
+                    commodity_id: Rc::clone(&commodity_id),
+                    region_id: Rc::clone(&region_id),
+                    year,
+                };
+                map.insert(key, demand);
+            }
+
+            Ok(())
+        };
+        errors.check(read_row())?;
+    }
+    errors.finish()?;
+
+    Ok((map, commodity_regions))
+}
+
+/// Linearly interpolate annual demand onto milestone years.
+///
+/// For milestone years outside the range of years provided for a given commodity + region pair,
+/// the demand for the nearest provided year is used (i.e. values are clamped rather than
+/// extrapolated).
+///
+/// # Arguments
+///
+/// * `demand` - Annual demand for arbitrary years
+/// * `milestone_years` - All milestone years
+///
+/// # Returns
+///
+/// Annual demand for every milestone year for each commodity + region pair in `demand`.
+fn interpolate_demand_to_milestone_years(
+    demand: &AnnualDemandMap,
+    milestone_years: &[u32],
+) -> AnnualDemandMap {
+    // Group the provided (year, demand) values by commodity + region
+    let mut grouped: HashMap<_, Vec<_>> = HashMap::new();
+    for (key, value) in demand.iter() {
+        grouped
+            .entry((Rc::clone(&key.commodity_id), Rc::clone(&key.region_id)))
+            .or_default()
+            .push((key.year, *value));
+    }
+
+    let mut map = AnnualDemandMap::new();
+    for ((commodity_id, region_id), mut values) in grouped {
+        values.sort_by_key(|(year, _)| *year);
+
+        for year in milestone_years.iter().copied() {
+            let value = match values.binary_search_by_key(&year, |(year, _)| *year) {
+                Ok(idx) => values[idx].1,
+                Err(0) => values[0].1,
+                Err(idx) if idx == values.len() => values[idx - 1].1,
+                Err(idx) => {
+                    let (year0, value0) = values[idx - 1];
+                    let (year1, value1) = values[idx];
+                    let frac = (year - year0) as f64 / (year1 - year0) as f64;
+                    value0 + frac * (value1 - value0)
+                }
+            };
+
+            let key = AnnualDemandMapKey {
+                commodity_id: Rc::clone(&commodity_id),
+                region_id: Rc::clone(&region_id),
+                year,
+            };
+            map.insert(key, value);
+        }
+    }
+
+    map
+}
+
 /// Calculate the demand for each combination of commodity, region, year and time slice.
 ///
 /// # Arguments
@@ -229,6 +481,7 @@ fn compute_demand_maps(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use float_cmp::assert_approx_eq;
     use itertools::iproduct;
     use std::fs::File;
     use std::io::Write;
@@ -275,8 +528,11 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             demand.into_iter(),
             &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &milestone_years
+            &milestone_years,
+            false,
+            false,
         )
         .is_ok());
 
@@ -298,8 +554,11 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             demand.into_iter(),
             &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &milestone_years
+            &milestone_years,
+            false,
+            false,
         )
         .is_err());
 
@@ -321,8 +580,11 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             demand.into_iter(),
             &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &milestone_years
+            &milestone_years,
+            false,
+            false,
         )
         .is_err());
 
@@ -344,8 +606,11 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             demand.into_iter(),
             &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &milestone_years
+            &milestone_years,
+            false,
+            false,
         )
         .is_err());
 
@@ -361,8 +626,11 @@ COM1,West,2020,13"
                 assert!(read_demand_from_iter(
                     demand.into_iter(),
                     &commodity_ids,
+                    &HashSet::new(),
                     &region_ids,
                     &milestone_years,
+                    false,
+                    false,
                 )
                 .is_err());
             };
@@ -397,8 +665,11 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             demand.into_iter(),
             &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &milestone_years
+            &milestone_years,
+            false,
+            false,
         )
         .is_err());
 
@@ -412,10 +683,163 @@ COM1,West,2020,13"
         assert!(read_demand_from_iter(
             iter::once(demand),
             &commodity_ids,
+            &HashSet::new(),
+            &region_ids,
+            &[2020, 2030],
+            false,
+            false,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_demand_from_iter_collect_all_errors() {
+        let commodity_ids = ["COM1".into()].into_iter().collect();
+        let region_ids = ["North".into()].into_iter().collect();
+        let milestone_years = [2020];
+        let demand = [
+            Demand {
+                year: 2020,
+                region_id: "Narnia".to_string(),
+                commodity_id: "COM1".to_string(),
+                demand: 10.0,
+            },
+            Demand {
+                year: 2020,
+                region_id: "North".to_string(),
+                commodity_id: "COM1".to_string(),
+                demand: -1.0,
+            },
+        ];
+        let read = |collect_all_errors| {
+            read_demand_from_iter(
+                demand.clone().into_iter(),
+                &commodity_ids,
+                &HashSet::new(),
+                &region_ids,
+                &milestone_years,
+                false,
+                collect_all_errors,
+            )
+            .unwrap_err()
+            .to_string()
+        };
+
+        // Fail fast: only the first error is reported
+        let message = read(false);
+        assert!(message.contains("Narnia"));
+        assert!(!message.contains("greater than zero"));
+
+        // Both errors are reported
+        let message = read(true);
+        assert!(message.starts_with("Found 2 errors"));
+        assert!(message.contains("Narnia"));
+        assert!(message.contains("greater than zero"));
+    }
+
+    #[test]
+    fn test_read_demand_from_iter_exportable() {
+        let commodity_ids = ["COM1".into()].into_iter().collect();
+        let exportable_ids = ["COM1".into()].into_iter().collect();
+        let region_ids = ["North".into()].into_iter().collect();
+        let milestone_years = [2020];
+
+        macro_rules! read_quantity {
+            ($quantity: expr) => {
+                read_demand_from_iter(
+                    iter::once(Demand {
+                        year: 2020,
+                        region_id: "North".to_string(),
+                        commodity_id: "COM1".to_string(),
+                        demand: $quantity,
+                    }),
+                    &commodity_ids,
+                    &exportable_ids,
+                    &region_ids,
+                    &milestone_years,
+                    false,
+                    false,
+                )
+            };
+        }
+
+        // Valid: negative demand represents exports
+        let (map, _) = read_quantity!(-10.0).unwrap();
+        let key = AnnualDemandMapKey {
+            commodity_id: "COM1".into(),
+            region_id: "North".into(),
+            year: 2020,
+        };
+        assert_eq!(map.get(&key), Some(&-10.0));
+
+        // Valid: positive demand is still allowed
+        assert!(read_quantity!(10.0).is_ok());
+
+        // Invalid quantities
+        assert!(read_quantity!(0.0).is_err());
+        assert!(read_quantity!(f64::NAN).is_err());
+        assert!(read_quantity!(f64::NEG_INFINITY).is_err());
+        assert!(read_quantity!(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_read_demand_from_iter_interpolate() {
+        let commodity_ids = ["COM1".into()].into_iter().collect();
+        let region_ids = ["North".into()].into_iter().collect();
+        let demand = [
+            Demand {
+                year: 2010,
+                region_id: "North".to_string(),
+                commodity_id: "COM1".to_string(),
+                demand: 10.0,
+            },
+            Demand {
+                year: 2030,
+                region_id: "North".to_string(),
+                commodity_id: "COM1".to_string(),
+                demand: 30.0,
+            },
+        ];
+
+        // Non-milestone years are not allowed without interpolation
+        assert!(read_demand_from_iter(
+            demand.clone().into_iter(),
+            &commodity_ids,
+            &HashSet::new(),
             &region_ids,
-            &[2020, 2030]
+            &[2020],
+            false,
+            false,
         )
         .is_err());
+
+        let (map, _) = read_demand_from_iter(
+            demand.into_iter(),
+            &commodity_ids,
+            &HashSet::new(),
+            &region_ids,
+            &[2000, 2020, 2030, 2040],
+            true,
+            false,
+        )
+        .unwrap();
+        let expected = AnnualDemandMap::from_iter(
+            [
+                (2000, 10.0), // Before provided range: clamped
+                (2020, 20.0), // Midpoint
+                (2030, 30.0), // Exact match
+                (2040, 30.0), // After provided range: clamped
+            ]
+            .map(|(year, demand)| {
+                let key = AnnualDemandMapKey {
+                    commodity_id: "COM1".into(),
+                    region_id: "North".into(),
+                    year,
+                };
+                (key, demand)
+            }),
+        );
+        assert_eq!(map, expected);
     }
 
     #[test]
@@ -460,11 +884,256 @@ COM1,West,2020,13"
                 13.0,
             ),
         ]);
-        let (demand, commodity_regions) =
-            read_demand_file(dir.path(), &commodity_ids, &region_ids, &milestone_years).unwrap();
+        let (demand, commodity_regions) = read_demand_file(
+            dir.path(),
+            &commodity_ids,
+            &HashSet::new(),
+            &region_ids,
+            &milestone_years,
+            &Settings::default(),
+        )
+        .unwrap();
         let commodity_regions_expected =
             iproduct!(commodity_ids.iter().cloned(), region_ids.iter().cloned()).collect();
         assert_eq!(demand, expected);
         assert_eq!(commodity_regions, commodity_regions_expected);
     }
+
+    #[test]
+    fn test_read_demand_file_commented_row() {
+        let dir = tempdir().unwrap();
+        {
+            let file_path = dir.path().join(DEMAND_FILE_NAME);
+            let mut file = File::create(file_path).unwrap();
+            writeln!(
+                file,
+                "commodity_id,region_id,year,demand
+COM1,North,2020,10
+#COM1,South,2020,11"
+            )
+            .unwrap();
+        }
+        let commodity_ids = HashSet::from_iter(iter::once("COM1".into()));
+        let region_ids = HashSet::from_iter(["North".into(), "South".into()]);
+        let milestone_years = [2020];
+
+        // Commented row is ignored
+        let settings = Settings {
+            allow_csv_comments: true,
+            ..Settings::default()
+        };
+        let (demand, _) = read_demand_file(
+            dir.path(),
+            &commodity_ids,
+            &HashSet::new(),
+            &region_ids,
+            &milestone_years,
+            &settings,
+        )
+        .unwrap();
+        let expected = AnnualDemandMap::from_iter([(
+            AnnualDemandMapKey {
+                commodity_id: "COM1".into(),
+                region_id: "North".into(),
+                year: 2020,
+            },
+            10.0,
+        )]);
+        assert_eq!(demand, expected);
+
+        // Comments are not allowed by default
+        assert!(read_demand_file(
+            dir.path(),
+            &commodity_ids,
+            &HashSet::new(),
+            &region_ids,
+            &milestone_years,
+            &Settings::default(),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_demand_growth_from_iter() {
+        let commodity_ids = ["COM1".into(), "COM2".into()].into_iter().collect();
+        let region_ids = ["North".into(), "South".into()].into_iter().collect();
+        let milestone_years = [2020, 2022];
+        let growth = |commodity_id: &str, region_id: &str| DemandGrowth {
+            commodity_id: commodity_id.into(),
+            region_id: region_id.into(),
+            base_year: 2020,
+            base_demand: 100.0,
+            annual_growth: 0.1,
+        };
+        let demand_commodity_regions = [("COM2".into(), "North".into())].into_iter().collect();
+
+        // Valid: demand compounds from the base year
+        let (demand, commodity_regions) = read_demand_growth_from_iter(
+            iter::once(growth("COM1", "North")),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &demand_commodity_regions,
+            false,
+        )
+        .unwrap();
+        let key = |year| AnnualDemandMapKey {
+            commodity_id: "COM1".into(),
+            region_id: "North".into(),
+            year,
+        };
+        assert_eq!(demand.len(), 2);
+        assert_approx_eq!(f64, demand[&key(2020)], 100.0);
+        assert_approx_eq!(f64, demand[&key(2022)], 121.0);
+        assert_eq!(
+            commodity_regions,
+            [("COM1".into(), "North".into())].into_iter().collect()
+        );
+
+        // Valid: base year after a milestone year
+        let (demand, _) = read_demand_growth_from_iter(
+            iter::once(DemandGrowth {
+                base_year: 2021,
+                ..growth("COM1", "North")
+            }),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &demand_commodity_regions,
+            false,
+        )
+        .unwrap();
+        assert_approx_eq!(f64, demand[&key(2020)], 100.0 / 1.1);
+        assert_approx_eq!(f64, demand[&key(2022)], 110.0);
+
+        // Invalid: demand for commodity also given in demand.csv (even for another region)
+        assert!(read_demand_growth_from_iter(
+            iter::once(growth("COM2", "South")),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &demand_commodity_regions,
+            false,
+        )
+        .is_err());
+
+        // Invalid: duplicate entries
+        assert!(read_demand_growth_from_iter(
+            [growth("COM1", "North"), growth("COM1", "North")].into_iter(),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &demand_commodity_regions,
+            false,
+        )
+        .is_err());
+
+        // Invalid: non-positive base demand
+        assert!(read_demand_growth_from_iter(
+            iter::once(DemandGrowth {
+                base_demand: 0.0,
+                ..growth("COM1", "North")
+            }),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &demand_commodity_regions,
+            false,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_demand_growth_from_iter_collect_all_errors() {
+        let commodity_ids = ["COM1".into()].into_iter().collect();
+        let region_ids = ["North".into()].into_iter().collect();
+        let growth = [
+            DemandGrowth {
+                commodity_id: "COM1".into(),
+                region_id: "Narnia".into(),
+                base_year: 2020,
+                base_demand: 100.0,
+                annual_growth: 0.1,
+            },
+            DemandGrowth {
+                commodity_id: "COM1".into(),
+                region_id: "North".into(),
+                base_year: 2020,
+                base_demand: -1.0,
+                annual_growth: 0.1,
+            },
+        ];
+        let read = |collect_all_errors| {
+            read_demand_growth_from_iter(
+                growth.clone().into_iter(),
+                &commodity_ids,
+                &region_ids,
+                &[2020],
+                &HashSet::new(),
+                collect_all_errors,
+            )
+            .unwrap_err()
+            .to_string()
+        };
+
+        // Fail fast: only the first error is reported
+        let message = read(false);
+        assert!(message.contains("Narnia"));
+        assert!(!message.contains("greater than zero"));
+
+        // Both errors are reported
+        let message = read(true);
+        assert!(message.starts_with("Found 2 errors"));
+        assert!(message.contains("Narnia"));
+        assert!(message.contains("greater than zero"));
+    }
+
+    #[test]
+    fn test_read_demand_growth_file_commented_row() {
+        let dir = tempdir().unwrap();
+        {
+            let file_path = dir.path().join(DEMAND_GROWTH_FILE_NAME);
+            let mut file = File::create(file_path).unwrap();
+            writeln!(
+                file,
+                "commodity_id,region_id,base_year,base_demand,annual_growth
+COM1,North,2020,100,0.1
+#COM1,South,2020,100,0.1"
+            )
+            .unwrap();
+        }
+        let commodity_ids = HashSet::from_iter(iter::once("COM1".into()));
+        let region_ids = HashSet::from_iter(["North".into(), "South".into()]);
+        let milestone_years = [2020];
+
+        // Commented row is ignored
+        let settings = Settings {
+            allow_csv_comments: true,
+            ..Settings::default()
+        };
+        let (_, commodity_regions) = read_demand_growth_file(
+            dir.path(),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &HashSet::new(),
+            &settings,
+        )
+        .unwrap();
+        assert_eq!(
+            commodity_regions,
+            [("COM1".into(), "North".into())].into_iter().collect()
+        );
+
+        // Comments are not allowed by default
+        assert!(read_demand_growth_file(
+            dir.path(),
+            &commodity_ids,
+            &region_ids,
+            &milestone_years,
+            &HashSet::new(),
+            &Settings::default(),
+        )
+        .is_err());
+    }
 }
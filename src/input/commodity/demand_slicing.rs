@@ -43,12 +43,14 @@ pub struct DemandSliceMapKey {
 /// * `region_ids` - All possible IDs for regions
 /// * `commodity_regions` - Pairs of commodities + regions listed in demand CSV file
 /// * `time_slice_info` - Information about seasons and times of day
+/// * `tolerance` - Tolerance for checking that demand fractions sum to one
 pub fn read_demand_slices(
     model_dir: &Path,
     commodity_ids: &HashSet<Rc<str>>,
     region_ids: &HashSet<Rc<str>>,
     commodity_regions: &CommodityRegionPairs,
     time_slice_info: &TimeSliceInfo,
+    tolerance: f64,
 ) -> Result<DemandSliceMap> {
     let file_path = model_dir.join(DEMAND_SLICING_FILE_NAME);
     let demand_slices_csv = read_csv(&file_path)?;
@@ -58,6 +60,7 @@ pub fn read_demand_slices(
         region_ids,
         commodity_regions,
         time_slice_info,
+        tolerance,
     )
     .with_context(|| input_err_msg(file_path))
 }
@@ -69,6 +72,7 @@ fn read_demand_slices_from_iter<I>(
     region_ids: &HashSet<Rc<str>>,
     commodity_regions: &CommodityRegionPairs,
     time_slice_info: &TimeSliceInfo,
+    tolerance: f64,
 ) -> Result<DemandSliceMap>
 where
     I: Iterator<Item = DemandSlice>,
@@ -104,7 +108,12 @@ where
         }
     }
 
-    validate_demand_slices(commodity_regions, &demand_slices, time_slice_info)?;
+    validate_demand_slices(
+        commodity_regions,
+        &demand_slices,
+        time_slice_info,
+        tolerance,
+    )?;
 
     Ok(demand_slices)
 }
@@ -116,11 +125,13 @@ where
 /// * It is non-empty
 /// * If an entry is provided for any commodity + region pair, there must be entries covering every
 ///   time slice
-/// * The demand fractions for all entries related to a commodity + region pair sum to one
+/// * The demand fractions for all entries related to a commodity + region pair sum to one (within
+///   `tolerance`)
 fn validate_demand_slices(
     commodity_regions: &CommodityRegionPairs,
     demand_slices: &DemandSliceMap,
     time_slice_info: &TimeSliceInfo,
+    tolerance: f64,
 ) -> Result<()> {
     for (commodity_id, region_id) in commodity_regions {
         time_slice_info
@@ -140,7 +151,8 @@ fn validate_demand_slices(
                 })
             })
             .process_results(|iter| {
-                check_fractions_sum_to_one(iter.copied()).context("Invalid demand fractions")
+                check_fractions_sum_to_one_with_tolerance(iter.copied(), tolerance)
+                    .context("Invalid demand fractions")
             })??;
     }
 
@@ -197,6 +209,7 @@ mod tests {
                 &region_ids,
                 &commodity_regions,
                 &time_slice_info,
+                1e-5,
             )
             .unwrap(),
             expected
@@ -307,6 +320,7 @@ mod tests {
                     &region_ids,
                     &commodity_regions,
                     &time_slice_info,
+                    1e-5,
                 )
                 .unwrap(),
                 expected
@@ -320,6 +334,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -336,6 +351,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -352,6 +368,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -368,6 +385,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -407,6 +425,7 @@ mod tests {
                 &region_ids,
                 &commodity_regions,
                 &time_slice_info,
+                1e-5,
             )
             .is_err());
         }
@@ -424,6 +443,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -440,6 +460,7 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
         )
         .is_err());
 
@@ -456,6 +477,33 @@ mod tests {
             &region_ids,
             &commodity_regions,
             &time_slice_info,
+            1e-5,
+        )
+        .is_err());
+
+        // Fractions sum to one within default tolerance, but not within a stricter one
+        let demand_slice = DemandSlice {
+            commodity_id: "COM1".into(),
+            region_id: "GBR".into(),
+            time_slice: "winter".into(),
+            fraction: 1.0 + 1e-9,
+        };
+        assert!(read_demand_slices_from_iter(
+            iter::once(demand_slice.clone()),
+            &commodity_ids,
+            &region_ids,
+            &commodity_regions,
+            &time_slice_info,
+            1e-5,
+        )
+        .is_ok());
+        assert!(read_demand_slices_from_iter(
+            iter::once(demand_slice),
+            &commodity_ids,
+            &region_ids,
+            &commodity_regions,
+            &time_slice_info,
+            1e-12,
         )
         .is_err());
 
@@ -472,6 +520,7 @@ mod tests {
             &region_ids,
             &HashSet::new(),
             &time_slice_info,
+            1e-5,
         )
         .is_err());
     }
@@ -3,6 +3,7 @@ use crate::commodity::Commodity;
 use crate::input::*;
 use crate::process::{Process, ProcessAvailability, ProcessFlow, ProcessParameter};
 use crate::region::RegionSelection;
+use crate::settings::Settings;
 use crate::time_slice::TimeSliceInfo;
 use anyhow::Result;
 use serde::Deserialize;
@@ -36,6 +37,9 @@ use define_process_id_getter;
 struct ProcessDescription {
     id: Rc<str>,
     description: String,
+    /// Whether the process is a sink, which only consumes commodities and has no output flows
+    #[serde(default)]
+    sink: bool,
 }
 define_id_getter! {ProcessDescription}
 
@@ -51,6 +55,7 @@ type GroupedMap<T> = HashMap<Rc<str>, Vec<T>>;
 /// * `region_ids` - All possible region IDs
 /// * `time_slice_info` - Information about seasons and times of day
 /// * `year_range` - The possible range of milestone years
+/// * `settings` - Program settings
 ///
 /// # Returns
 ///
@@ -61,13 +66,31 @@ pub fn read_processes(
     region_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
     year_range: &RangeInclusive<u32>,
+    settings: &Settings,
 ) -> Result<HashMap<Rc<str>, Rc<Process>>> {
     let file_path = model_dir.join(PROCESSES_FILE_NAME);
     let descriptions = read_csv_id_file::<ProcessDescription>(&file_path)?;
     let process_ids = HashSet::from_iter(descriptions.keys().cloned());
+    let sink_process_ids = descriptions
+        .values()
+        .filter(|description| description.sink)
+        .map(|description| Rc::clone(&description.id))
+        .collect();
 
-    let availabilities = read_process_availabilities(model_dir, &process_ids, time_slice_info)?;
-    let flows = read_process_flows(model_dir, &process_ids, commodities)?;
+    let availabilities = read_process_availabilities(
+        model_dir,
+        &process_ids,
+        time_slice_info,
+        settings.distribute_seasonal_availability,
+    )?;
+    let flows = read_process_flows(
+        model_dir,
+        &process_ids,
+        &sink_process_ids,
+        commodities,
+        settings.collect_all_errors,
+        settings.allow_csv_comments,
+    )?;
     let parameters = read_process_parameters(model_dir, &process_ids, year_range)?;
     let regions = read_process_regions(model_dir, &process_ids, region_ids)?;
 
@@ -144,10 +167,12 @@ mod tests {
             ProcessDescription {
                 id: Rc::from("process1"),
                 description: "Process 1".to_string(),
+                sink: false,
             },
             ProcessDescription {
                 id: Rc::from("process2"),
                 description: "Process 2".to_string(),
+                sink: false,
             },
         ];
 
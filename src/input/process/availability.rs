@@ -2,7 +2,7 @@
 use super::define_process_id_getter;
 use crate::input::*;
 use crate::process::{LimitType, ProcessAvailability};
-use crate::time_slice::TimeSliceInfo;
+use crate::time_slice::{TimeSliceInfo, TimeSliceSelection};
 use anyhow::{Context, Result};
 use itertools::Itertools;
 use serde::Deserialize;
@@ -20,39 +20,147 @@ struct ProcessAvailabilityRaw {
     process_id: String,
     limit_type: LimitType,
     time_slice: String,
-    #[serde(deserialize_with = "deserialise_proportion_nonzero")]
+    #[serde(deserialize_with = "deserialise_percentage_nonzero")]
     value: f64,
 }
 
 /// Read the availability of each process over time slices
+///
+/// # Arguments
+///
+/// * `model_dir` - Folder containing model configuration files
+/// * `process_ids` - All possible process IDs
+/// * `time_slice_info` - Information about seasons and times of day
+/// * `distribute_seasonal` - Whether to distribute seasonal availabilities across the constituent
+///   time slices
 pub fn read_process_availabilities(
     model_dir: &Path,
     process_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
+    distribute_seasonal: bool,
 ) -> Result<HashMap<Rc<str>, Vec<ProcessAvailability>>> {
     let file_path = model_dir.join(PROCESS_AVAILABILITIES_FILE_NAME);
     let process_availabilities_csv = read_csv(&file_path)?;
-    read_process_availabilities_from_iter(process_availabilities_csv, process_ids, time_slice_info)
-        .with_context(|| input_err_msg(&file_path))
+    read_process_availabilities_from_iter(
+        process_availabilities_csv,
+        process_ids,
+        time_slice_info,
+        distribute_seasonal,
+    )
+    .with_context(|| input_err_msg(&file_path))
 }
 
 fn read_process_availabilities_from_iter<I>(
     iter: I,
     process_ids: &HashSet<Rc<str>>,
     time_slice_info: &TimeSliceInfo,
+    distribute_seasonal: bool,
 ) -> Result<HashMap<Rc<str>, Vec<ProcessAvailability>>>
 where
     I: Iterator<Item = ProcessAvailabilityRaw>,
 {
     iter.map(|record| -> Result<_> {
         let time_slice = time_slice_info.get_selection(&record.time_slice)?;
+        if !distribute_seasonal || !matches!(time_slice, TimeSliceSelection::Season(_)) {
+            return Ok(vec![ProcessAvailability {
+                process_id: record.process_id,
+                limit_type: record.limit_type,
+                time_slice,
+                value: record.value,
+            }]);
+        }
 
-        Ok(ProcessAvailability {
-            process_id: record.process_id,
-            limit_type: record.limit_type,
-            time_slice,
-            value: record.value,
-        })
+        // Share the availability between the season's time slices in proportion to duration
+        Ok(time_slice_info
+            .calculate_share(&time_slice, record.value)
+            .map(|(ts, value)| ProcessAvailability {
+                process_id: record.process_id.clone(),
+                limit_type: record.limit_type.clone(),
+                time_slice: TimeSliceSelection::Single(ts.clone()),
+                value,
+            })
+            .collect_vec())
     })
+    .flatten_ok()
     .process_results(|iter| iter.into_id_map(process_ids))?
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_slice::TimeSliceID;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_read_process_availabilities_from_iter_distribute_seasonal() {
+        let slices = [
+            TimeSliceID {
+                season: "winter".into(),
+                time_of_day: "day".into(),
+            },
+            TimeSliceID {
+                season: "winter".into(),
+                time_of_day: "night".into(),
+            },
+            TimeSliceID {
+                season: "summer".into(),
+                time_of_day: "day".into(),
+            },
+        ];
+        let time_slice_info = TimeSliceInfo {
+            seasons: ["winter".into(), "summer".into()].into_iter().collect(),
+            times_of_day: ["day".into(), "night".into()].into_iter().collect(),
+            fractions: [
+                (slices[0].clone(), 0.1),
+                (slices[1].clone(), 0.3),
+                (slices[2].clone(), 0.6),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        let process_ids = ["process1".into()].into_iter().collect();
+        let record = || ProcessAvailabilityRaw {
+            process_id: "process1".into(),
+            limit_type: LimitType::UpperBound,
+            time_slice: "winter".into(),
+            value: 0.8,
+        };
+
+        // Without distribution, the seasonal limit is kept as is
+        let availabilities = read_process_availabilities_from_iter(
+            [record()].into_iter(),
+            &process_ids,
+            &time_slice_info,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            availabilities["process1"],
+            [ProcessAvailability {
+                process_id: "process1".into(),
+                limit_type: LimitType::UpperBound,
+                time_slice: TimeSliceSelection::Season("winter".into()),
+                value: 0.8,
+            }]
+        );
+
+        // With distribution, the limit is shared between winter's time slices
+        let availabilities = read_process_availabilities_from_iter(
+            [record()].into_iter(),
+            &process_ids,
+            &time_slice_info,
+            true,
+        )
+        .unwrap();
+        let availabilities = &availabilities["process1"];
+        assert_eq!(availabilities.len(), 2);
+        for (ts, expected) in [(&slices[0], 0.2), (&slices[1], 0.6)] {
+            let availability = availabilities
+                .iter()
+                .find(|avail| avail.time_slice == TimeSliceSelection::Single(ts.clone()))
+                .unwrap();
+            assert_eq!(availability.limit_type, LimitType::UpperBound);
+            assert_approx_eq!(f64, availability.value, expected);
+        }
+    }
+}
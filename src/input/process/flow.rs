@@ -4,7 +4,6 @@ use crate::commodity::Commodity;
 use crate::input::*;
 use crate::process::{FlowType, ProcessFlow};
 use anyhow::{ensure, Context, Result};
-use itertools::Itertools;
 use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
@@ -27,28 +26,51 @@ struct ProcessFlowRaw {
 define_process_id_getter! {ProcessFlowRaw}
 
 /// Read process flows from a CSV file
+///
+/// # Arguments
+///
+/// * `model_dir` - Folder containing model configuration files
+/// * `process_ids` - All possible process IDs
+/// * `sink_process_ids` - IDs of sink processes, which are allowed to have no output flows
+/// * `commodities` - Commodities for the model
+/// * `collect_all_errors` - Whether to report all invalid rows together rather than failing on the
+///   first one
+/// * `allow_comments` - Whether to ignore rows beginning with `#`
 pub fn read_process_flows(
     model_dir: &Path,
     process_ids: &HashSet<Rc<str>>,
+    sink_process_ids: &HashSet<Rc<str>>,
     commodities: &HashMap<Rc<str>, Rc<Commodity>>,
+    collect_all_errors: bool,
+    allow_comments: bool,
 ) -> Result<HashMap<Rc<str>, Vec<ProcessFlow>>> {
     let file_path = model_dir.join(PROCESS_FLOWS_FILE_NAME);
-    let process_flow_csv = read_csv(&file_path)?;
-    read_process_flows_from_iter(process_flow_csv, process_ids, commodities)
-        .with_context(|| input_err_msg(&file_path))
+    let process_flow_csv = read_csv_with_comments(&file_path, allow_comments)?;
+    read_process_flows_from_iter(
+        process_flow_csv,
+        process_ids,
+        sink_process_ids,
+        commodities,
+        collect_all_errors,
+    )
+    .with_context(|| input_err_msg(&file_path))
 }
 
 /// Read 'ProcessFlowRaw' records from an iterator and convert them into 'ProcessFlow' records.
 fn read_process_flows_from_iter<I>(
     iter: I,
     process_ids: &HashSet<Rc<str>>,
+    sink_process_ids: &HashSet<Rc<str>>,
     commodities: &HashMap<Rc<str>, Rc<Commodity>>,
+    collect_all_errors: bool,
 ) -> Result<HashMap<Rc<str>, Vec<ProcessFlow>>>
 where
     I: Iterator<Item = ProcessFlowRaw>,
 {
-    let flows = iter
-        .map(|flow| -> Result<ProcessFlow> {
+    let mut errors = ErrorCollector::new(collect_all_errors);
+    let mut flows = Vec::new();
+    for flow in iter {
+        let read_row = || -> Result<ProcessFlow> {
             let commodity = commodities
                 .get(flow.commodity_id.as_str())
                 .with_context(|| format!("{} is not a valid commodity ID", &flow.commodity_id))?;
@@ -83,11 +105,17 @@ where
                 flow_cost: flow.flow_cost.unwrap_or(0.0),
                 is_pac: flow.is_pac,
             })
-        })
-        .process_results(|iter| iter.into_id_map(process_ids))??;
+        };
+        if let Some(flow) = errors.check(read_row())? {
+            flows.push(flow);
+        }
+    }
+    errors.finish()?;
+    let flows = flows.into_iter().into_id_map(process_ids)?;
 
     validate_flows(&flows)?;
     validate_pac_flows(&flows)?;
+    validate_output_flows(&flows, sink_process_ids)?;
 
     Ok(flows)
 }
@@ -148,31 +176,45 @@ fn validate_pac_flows(flows: &HashMap<Rc<str>, Vec<ProcessFlow>>) -> Result<()>
     Ok(())
 }
 
+/// Validate that every process has at least one output flow, unless it is a sink process.
+///
+/// Processes are checked in order of ID, so that the same process is reported each time.
+///
+/// # Arguments
+///
+/// * `flows` - A map of process IDs to process flows
+/// * `sink_process_ids` - IDs of sink processes, which are allowed to have no output flows
+///
+/// # Returns
+/// An `Ok(())` if the check is successful, or an error.
+fn validate_output_flows(
+    flows: &HashMap<Rc<str>, Vec<ProcessFlow>>,
+    sink_process_ids: &HashSet<Rc<str>>,
+) -> Result<()> {
+    for (process_id, flows) in flows
+        .iter()
+        .filter(|(process_id, _)| !sink_process_ids.contains(*process_id))
+        .sorted_by_key(|(process_id, _)| *process_id)
+    {
+        ensure!(
+            flows.iter().any(|flow| flow.is_output()),
+            "Process {process_id} has no output flows and is not a sink"
+        );
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::commodity::{CommodityCostMap, CommodityType, DemandMap};
-    use crate::time_slice::TimeSliceLevel;
+    use crate::fixture::create_commodity_map;
     use std::iter;
 
     #[test]
     fn test_read_process_flows_from_iter_good() {
         let process_ids = ["id1".into(), "id2".into()].into_iter().collect();
-        let commodities: HashMap<Rc<str>, Rc<Commodity>> = ["commodity1", "commodity2"]
-            .into_iter()
-            .map(|id| {
-                let commodity = Commodity {
-                    id: id.into(),
-                    description: "Some description".into(),
-                    kind: CommodityType::InputCommodity,
-                    time_slice_level: TimeSliceLevel::Annual,
-                    costs: CommodityCostMap::new(),
-                    demand: DemandMap::new(),
-                };
-
-                (Rc::clone(&commodity.id), commodity.into())
-            })
-            .collect();
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
 
         let flows_raw = [
             ProcessFlowRaw {
@@ -236,30 +278,21 @@ mod tests {
             ),
         ]);
 
-        let actual =
-            read_process_flows_from_iter(flows_raw.into_iter(), &process_ids, &commodities)
-                .unwrap();
+        let actual = read_process_flows_from_iter(
+            flows_raw.into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false,
+        )
+        .unwrap();
         assert_eq!(expected, actual);
     }
 
     #[test]
     fn test_read_process_flows_from_iter_bad_commodity_id() {
         let process_ids = ["id1".into(), "id2".into()].into_iter().collect();
-        let commodities = ["commodity1", "commodity2"]
-            .into_iter()
-            .map(|id| {
-                let commodity = Commodity {
-                    id: id.into(),
-                    description: "Some description".into(),
-                    kind: CommodityType::InputCommodity,
-                    time_slice_level: TimeSliceLevel::Annual,
-                    costs: CommodityCostMap::new(),
-                    demand: DemandMap::new(),
-                };
-
-                (Rc::clone(&commodity.id), commodity.into())
-            })
-            .collect();
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
 
         let flows_raw = [
             ProcessFlowRaw {
@@ -280,25 +313,20 @@ mod tests {
             },
         ];
 
-        assert!(
-            read_process_flows_from_iter(flows_raw.into_iter(), &process_ids, &commodities)
-                .is_err()
-        );
+        assert!(read_process_flows_from_iter(
+            flows_raw.into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false
+        )
+        .is_err());
     }
 
     #[test]
     fn test_read_process_flows_from_iter_bad_flow() {
         let process_ids = iter::once("id1".into()).collect();
-        let commodities = iter::once(Commodity {
-            id: "commodity1".into(),
-            description: "Some description".into(),
-            kind: CommodityType::InputCommodity,
-            time_slice_level: TimeSliceLevel::Annual,
-            costs: CommodityCostMap::new(),
-            demand: DemandMap::new(),
-        })
-        .map(|c| (c.id.clone(), Rc::new(c)))
-        .collect();
+        let commodities = create_commodity_map(&["commodity1"]);
 
         macro_rules! check_bad_flow {
             ($flow:expr) => {
@@ -310,10 +338,14 @@ mod tests {
                     flow_cost: Some(1.0),
                     is_pac: true,
                 };
-                assert!(
-                    read_process_flows_from_iter(iter::once(flow), &process_ids, &commodities)
-                        .is_err()
-                );
+                assert!(read_process_flows_from_iter(
+                    iter::once(flow),
+                    &process_ids,
+                    &HashSet::new(),
+                    &commodities,
+                    false
+                )
+                .is_err());
             };
         }
 
@@ -326,21 +358,7 @@ mod tests {
     #[test]
     fn test_read_process_flows_from_iter_bad_pacs() {
         let process_ids = ["id1".into(), "id2".into()].into_iter().collect();
-        let commodities = ["commodity1", "commodity2"]
-            .into_iter()
-            .map(|id| {
-                let commodity = Commodity {
-                    id: id.into(),
-                    description: "Some description".into(),
-                    kind: CommodityType::InputCommodity,
-                    time_slice_level: TimeSliceLevel::Annual,
-                    costs: CommodityCostMap::new(),
-                    demand: DemandMap::new(),
-                };
-
-                (Rc::clone(&commodity.id), commodity.into())
-            })
-            .collect();
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
 
         let flows_raw = [
             ProcessFlowRaw {
@@ -361,30 +379,20 @@ mod tests {
             },
         ];
 
-        assert!(
-            read_process_flows_from_iter(flows_raw.into_iter(), &process_ids, &commodities)
-                .is_err()
-        );
+        assert!(read_process_flows_from_iter(
+            flows_raw.into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false
+        )
+        .is_err());
     }
 
     #[test]
     fn test_read_process_flows_from_iter_no_pacs() {
         let process_ids = ["id1".into(), "id2".into()].into_iter().collect();
-        let commodities = ["commodity1", "commodity2"]
-            .into_iter()
-            .map(|id| {
-                let commodity = Commodity {
-                    id: id.into(),
-                    description: "Some description".into(),
-                    kind: CommodityType::InputCommodity,
-                    time_slice_level: TimeSliceLevel::Annual,
-                    costs: CommodityCostMap::new(),
-                    demand: DemandMap::new(),
-                };
-
-                (Rc::clone(&commodity.id), commodity.into())
-            })
-            .collect();
+        let commodities = create_commodity_map(&["commodity1", "commodity2"]);
 
         let flows_raw = [
             ProcessFlowRaw {
@@ -405,25 +413,76 @@ mod tests {
             },
         ];
 
-        assert!(
-            read_process_flows_from_iter(flows_raw.into_iter(), &process_ids, &commodities)
-                .is_err()
+        assert!(read_process_flows_from_iter(
+            flows_raw.into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_read_process_flows_from_iter_no_outputs() {
+        let process_ids = ["id1".into(), "id2".into(), "id3".into()]
+            .into_iter()
+            .collect();
+        let commodities = create_commodity_map(&["commodity1"]);
+
+        let create_flows = || {
+            ["id3", "id1", "id2"].map(|process_id| ProcessFlowRaw {
+                process_id: process_id.into(),
+                commodity_id: "commodity1".into(),
+                flow: -1.0,
+                flow_type: FlowType::Fixed,
+                flow_cost: Some(1.0),
+                is_pac: true,
+            })
+        };
+
+        // The process with the lowest ID is always reported
+        let err = read_process_flows_from_iter(
+            create_flows().into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Process id1 has no output flows and is not a sink"
+        );
+
+        // Sink processes don't need any outputs
+        let sink_process_ids = ["id1".into(), "id2".into()].into_iter().collect();
+        let err = read_process_flows_from_iter(
+            create_flows().into_iter(),
+            &process_ids,
+            &sink_process_ids,
+            &commodities,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Process id3 has no output flows and is not a sink"
         );
+        assert!(read_process_flows_from_iter(
+            create_flows().into_iter(),
+            &process_ids,
+            &process_ids,
+            &commodities,
+            false
+        )
+        .is_ok());
     }
 
     #[test]
     fn test_read_process_flows_from_iter_flow_cost() {
         let process_ids = iter::once("id1".into()).collect();
-        let commodities = iter::once(Commodity {
-            id: "commodity1".into(),
-            description: "Some description".into(),
-            kind: CommodityType::InputCommodity,
-            time_slice_level: TimeSliceLevel::Annual,
-            costs: CommodityCostMap::new(),
-            demand: DemandMap::new(),
-        })
-        .map(|c| (c.id.clone(), Rc::new(c)))
-        .collect();
+        let commodities = create_commodity_map(&["commodity1"]);
 
         macro_rules! is_flow_cost_ok {
             ($flow_cost:expr) => {{
@@ -436,7 +495,14 @@ mod tests {
                     is_pac: true,
                 };
 
-                read_process_flows_from_iter(iter::once(flow), &process_ids, &commodities).is_ok()
+                read_process_flows_from_iter(
+                    iter::once(flow),
+                    &process_ids,
+                    &HashSet::new(),
+                    &commodities,
+                    false,
+                )
+                .is_ok()
             }};
         }
 
@@ -451,21 +517,7 @@ mod tests {
     #[test]
     fn test_read_process_flows_from_iter_duplicate_flow() {
         let process_ids = iter::once("id1".into()).collect();
-        let commodities = ["commodity1"]
-            .into_iter()
-            .map(|id| {
-                let commodity = Commodity {
-                    id: id.into(),
-                    description: "Some description".into(),
-                    kind: CommodityType::InputCommodity,
-                    time_slice_level: TimeSliceLevel::Annual,
-                    costs: CommodityCostMap::new(),
-                    demand: DemandMap::new(),
-                };
-
-                (Rc::clone(&commodity.id), commodity.into())
-            })
-            .collect();
+        let commodities = create_commodity_map(&["commodity1"]);
 
         let flows_raw = [
             ProcessFlowRaw {
@@ -486,9 +538,13 @@ mod tests {
             },
         ];
 
-        assert!(
-            read_process_flows_from_iter(flows_raw.into_iter(), &process_ids, &commodities)
-                .is_err()
-        );
+        assert!(read_process_flows_from_iter(
+            flows_raw.into_iter(),
+            &process_ids,
+            &HashSet::new(),
+            &commodities,
+            false
+        )
+        .is_err());
     }
 }
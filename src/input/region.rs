@@ -3,14 +3,23 @@ use super::*;
 use crate::region::{Region, RegionSelection};
 use anyhow::{ensure, Context, Result};
 use serde::de::DeserializeOwned;
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::rc::Rc;
 
 const REGIONS_FILE_NAME: &str = "regions.csv";
+const REGION_ADJACENCY_FILE_NAME: &str = "region_adjacency.csv";
 
 define_id_getter! {Region}
 
+/// Represents a row of the region adjacency CSV file
+#[derive(PartialEq, Debug, Deserialize)]
+struct RegionAdjacency {
+    region_id: String,
+    neighbour_id: String,
+}
+
 /// An object which is associated with a single region
 pub trait HasRegionID {
     /// Get the associated region ID
@@ -30,6 +39,8 @@ pub(crate) use define_region_id_getter;
 
 /// Reads regions from a CSV file.
 ///
+/// If a region adjacency file is present, the neighbours of each region are also read.
+///
 /// # Arguments
 ///
 /// * `model_dir` - Folder containing model configuration files
@@ -38,7 +49,65 @@ pub(crate) use define_region_id_getter;
 ///
 /// A `HashMap<Rc<str>, Region>` with the parsed regions data or an error. The keys are region IDs.
 pub fn read_regions(model_dir: &Path) -> Result<HashMap<Rc<str>, Region>> {
-    read_csv_id_file(&model_dir.join(REGIONS_FILE_NAME))
+    let mut regions: HashMap<Rc<str>, Region> =
+        read_csv_id_file(&model_dir.join(REGIONS_FILE_NAME))?;
+
+    let file_path = model_dir.join(REGION_ADJACENCY_FILE_NAME);
+    if file_path.is_file() {
+        let region_ids = regions.keys().cloned().collect();
+        let adjacency = read_region_adjacency_from_iter(read_csv(&file_path)?, &region_ids)
+            .with_context(|| input_err_msg(&file_path))?;
+        for (region_id, neighbours) in adjacency {
+            regions.get_mut(&region_id).unwrap().neighbours = neighbours;
+        }
+    }
+
+    Ok(regions)
+}
+
+/// Read region adjacency information from an iterator.
+///
+/// # Arguments
+///
+/// * `iter` - An iterator of [`RegionAdjacency`]s
+/// * `region_ids` - All possible valid region IDs
+///
+/// # Returns
+///
+/// A map of region IDs to the IDs of their neighbours or an error if a region is unknown, a region
+/// is listed as its own neighbour or the relation is not symmetric.
+fn read_region_adjacency_from_iter<I>(
+    iter: I,
+    region_ids: &HashSet<Rc<str>>,
+) -> Result<HashMap<Rc<str>, HashSet<Rc<str>>>>
+where
+    I: Iterator<Item = RegionAdjacency>,
+{
+    let mut adjacency: HashMap<Rc<str>, HashSet<Rc<str>>> = HashMap::new();
+    for entry in iter {
+        let region_id = region_ids.get_id(&entry.region_id)?;
+        let neighbour_id = region_ids.get_id(&entry.neighbour_id)?;
+        ensure!(
+            region_id != neighbour_id,
+            "Region {region_id} cannot be its own neighbour"
+        );
+
+        adjacency.entry(region_id).or_default().insert(neighbour_id);
+    }
+
+    for (region_id, neighbours) in adjacency.iter().sorted_by_key(|(id, _)| *id) {
+        for neighbour_id in neighbours.iter().sorted() {
+            ensure!(
+                adjacency
+                    .get(neighbour_id)
+                    .is_some_and(|other| other.contains(region_id)),
+                "Region adjacency must be symmetric: {region_id} is adjacent to {neighbour_id}, \
+                but not vice versa"
+            );
+        }
+    }
+
+    Ok(adjacency)
 }
 
 /// Read region IDs associated with a particular entity.
@@ -125,7 +194,6 @@ fn try_insert_region(
 #[cfg(test)]
 mod tests {
     use super::*;
-    use serde::Deserialize;
     use std::fs::File;
     use std::io::Write;
     use std::path::Path;
@@ -158,6 +226,7 @@ AP,Asia Pacific"
                     Region {
                         id: "NA".into(),
                         description: "North America".to_string(),
+                        neighbours: HashSet::new(),
                     }
                 ),
                 (
@@ -165,6 +234,7 @@ AP,Asia Pacific"
                     Region {
                         id: "EU".into(),
                         description: "Europe".to_string(),
+                        neighbours: HashSet::new(),
                     }
                 ),
                 (
@@ -172,12 +242,74 @@ AP,Asia Pacific"
                     Region {
                         id: "AP".into(),
                         description: "Asia Pacific".to_string(),
+                        neighbours: HashSet::new(),
                     }
                 ),
             ])
         )
     }
 
+    #[test]
+    fn test_read_regions_with_adjacency() {
+        let dir = tempdir().unwrap();
+        create_regions_file(dir.path());
+        {
+            let file_path = dir.path().join(REGION_ADJACENCY_FILE_NAME);
+            let mut file = File::create(file_path).unwrap();
+            writeln!(file, "region_id,neighbour_id\nNA,EU\nEU,NA").unwrap();
+        }
+
+        let regions = read_regions(dir.path()).unwrap();
+        assert!(regions["NA"].is_neighbour("EU"));
+        assert!(regions["EU"].is_neighbour("NA"));
+        assert!(!regions["NA"].is_neighbour("AP"));
+        assert!(regions["AP"].neighbours.is_empty());
+    }
+
+    #[test]
+    fn test_read_region_adjacency_from_iter() {
+        let region_ids = ["GBR".into(), "FRA".into(), "DEU".into()]
+            .into_iter()
+            .collect();
+        let adjacency = |region_id: &str, neighbour_id: &str| RegionAdjacency {
+            region_id: region_id.into(),
+            neighbour_id: neighbour_id.into(),
+        };
+
+        // Valid symmetric adjacency
+        let entries = [
+            adjacency("GBR", "FRA"),
+            adjacency("FRA", "GBR"),
+            adjacency("FRA", "DEU"),
+            adjacency("DEU", "FRA"),
+        ];
+        let expected = HashMap::from([
+            ("GBR".into(), HashSet::from(["FRA".into()])),
+            ("FRA".into(), HashSet::from(["GBR".into(), "DEU".into()])),
+            ("DEU".into(), HashSet::from(["FRA".into()])),
+        ]);
+        assert_eq!(
+            read_region_adjacency_from_iter(entries.into_iter(), &region_ids).unwrap(),
+            expected
+        );
+
+        // Asymmetric
+        let entries = [
+            adjacency("GBR", "FRA"),
+            adjacency("FRA", "GBR"),
+            adjacency("FRA", "DEU"),
+        ];
+        assert!(read_region_adjacency_from_iter(entries.into_iter(), &region_ids).is_err());
+
+        // Unknown region
+        let entries = [adjacency("GBR", "ESP"), adjacency("ESP", "GBR")];
+        assert!(read_region_adjacency_from_iter(entries.into_iter(), &region_ids).is_err());
+
+        // Region adjacent to itself
+        let entries = [adjacency("GBR", "GBR")];
+        assert!(read_region_adjacency_from_iter(entries.into_iter(), &region_ids).is_err());
+    }
+
     #[test]
     fn test_try_insert_region() {
         let region_ids = ["GBR".into(), "FRA".into()].into_iter().collect();
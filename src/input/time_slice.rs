@@ -3,7 +3,6 @@
 use crate::input::*;
 use anyhow::{ensure, Context, Result};
 use serde::Deserialize;
-use serde_string_enum::DeserializeLabeledStringEnum;
 use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::rc::Rc;
@@ -56,26 +55,14 @@ where
         );
     }
 
-    // Validate data
-    check_fractions_sum_to_one(fractions.values().cloned())
-        .context("Invalid time slice fractions")?;
-
-    Ok(TimeSliceInfo {
+    let info = TimeSliceInfo {
         seasons,
         times_of_day,
         fractions,
-    })
-}
+    };
+    info.validate()?;
 
-/// Refers to a particular aspect of a time slice
-#[derive(PartialEq, Debug, DeserializeLabeledStringEnum)]
-pub enum TimeSliceLevel {
-    #[string = "annual"]
-    Annual,
-    #[string = "season"]
-    Season,
-    #[string = "daynight"]
-    DayNight,
+    Ok(info)
 }
 
 /// Read time slices from a CSV file.
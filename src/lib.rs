@@ -3,11 +3,17 @@
 pub mod agent;
 pub mod commands;
 pub mod commodity;
+pub mod finance;
+#[cfg(test)]
+mod fixture;
 pub mod input;
 pub mod log;
 pub mod model;
+pub mod output;
 pub mod process;
+pub mod random;
 pub mod region;
 pub mod settings;
 pub mod simulation;
 pub mod time_slice;
+pub mod year;
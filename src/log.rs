@@ -3,24 +3,95 @@
 //! This module sets up logging with various levels (error, warn, info, debug, trace) and optional
 //! colourisation based on terminal support. It also allows configuration of the log level through
 //! environment variables.
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Local;
+use clap::ValueEnum;
 use fern::colors::{Color, ColoredLevelConfig};
 use fern::Dispatch;
+use log::{Level, LevelFilter, Record};
+use serde_json::json;
 use std::env;
+use std::fmt::{Arguments, Display, Formatter};
+use std::fs::File;
 use std::io::IsTerminal;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 /// The default log level for the program.
 ///
 /// Note that we disable logging when running tests.
 const DEFAULT_LOG_LEVEL: &str = if cfg!(test) { "off" } else { "info" };
 
+/// Whether the program logger has been initialised
+static LOGGER_INITIALISED: AtomicBool = AtomicBool::new(false);
+
+/// The format in which log records are written
+#[derive(Clone, Copy, Debug, Default, PartialEq, ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable text, coloured if the output is a terminal
+    #[default]
+    Text,
+    /// One JSON object per record, with timestamp, level, target and message fields
+    Json,
+}
+
+/// Formats a value with an SI suffix (k, M, G or T) to make large values in log messages easier
+/// to read.
+///
+/// Values smaller than 1000 are written without a suffix. The default precision for scaled values
+/// is two decimal places, which can be overridden with the usual syntax (e.g. `{:.1}`). This is
+/// only intended for human-facing output and should not be used when writing output files.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SIValue(pub f64);
+
+impl Display for SIValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        const SUFFIXES: [(f64, &str); 4] = [(1e12, "T"), (1e9, "G"), (1e6, "M"), (1e3, "k")];
+
+        let value = self.0;
+        if value.is_finite() {
+            if let Some((scale, suffix)) = SUFFIXES
+                .into_iter()
+                .find(|(scale, _)| value.abs() >= *scale)
+            {
+                let precision = f.precision().unwrap_or(2);
+                return write!(f, "{:.*}{suffix}", precision, value / scale);
+            }
+        }
+
+        match f.precision() {
+            Some(precision) => write!(f, "{value:.precision$}"),
+            None => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Format a log record as a single-line JSON object.
+///
+/// # Arguments
+///
+/// * `timestamp` - When the record was emitted
+/// * `level` - The log level of the record
+/// * `target` - The target of the record (usually the module path)
+/// * `message` - The log message
+fn format_json_record(timestamp: &str, level: Level, target: &str, message: &str) -> String {
+    json!({
+        "timestamp": timestamp,
+        "level": level.as_str(),
+        "target": target,
+        "message": message,
+    })
+    .to_string()
+}
+
 /// Initialise the program logger using the `fern` logging library with colourised output.
 ///
 /// The user can specify their preferred logging level via the `settings.toml` file (defaulting to
 /// `info` if not present) or with the `MUSE2_LOG_LEVEL` environment variable. If both are provided,
 /// the environment variable takes precedence.
 ///
+/// Log records are always written to stdout and, if `log_file_path` is provided, to that file too.
+///
 /// Possible log level options are:
 ///
 /// * `error`
@@ -32,7 +103,13 @@ const DEFAULT_LOG_LEVEL: &str = if cfg!(test) { "off" } else { "info" };
 /// # Arguments
 ///
 /// * `log_level_from_settings`: The log level specified in `settings.toml`
-pub fn init(log_level_from_settings: Option<&str>) -> Result<()> {
+/// * `log_format`: The format in which to write log records
+/// * `log_file_path`: If provided, records are also written to this file
+pub fn init(
+    log_level_from_settings: Option<&str>,
+    log_format: LogFormat,
+    log_file_path: Option<&Path>,
+) -> Result<()> {
     // Retrieve the log level from the environment variable or settings, or use the default
     let log_level = env::var("MUSE2_LOG_LEVEL").unwrap_or_else(|_| {
         log_level_from_settings
@@ -42,59 +119,198 @@ pub fn init(log_level_from_settings: Option<&str>) -> Result<()> {
 
     // Convert the log level string to a log::LevelFilter
     let log_level = match log_level.to_lowercase().as_str() {
-        "off" => log::LevelFilter::Off,
-        "error" => log::LevelFilter::Error,
-        "warn" => log::LevelFilter::Warn,
-        "info" => log::LevelFilter::Info,
-        "debug" => log::LevelFilter::Debug,
-        "trace" => log::LevelFilter::Trace,
+        "off" => LevelFilter::Off,
+        "error" => LevelFilter::Error,
+        "warn" => LevelFilter::Warn,
+        "info" => LevelFilter::Info,
+        "debug" => LevelFilter::Debug,
+        "trace" => LevelFilter::Trace,
         unknown => bail!("Unknown log level: {}", unknown),
     };
 
-    // Format timestamp as HH:MM:SS
-    let timestamp_format = "%H:%M:%S";
-
     // Automatically apply colours only if the output is a terminal
     let use_colour = std::io::stdout().is_terminal();
 
-    // Set up colours for log levels
-    let colours = ColoredLevelConfig::new()
-        .error(Color::Red)
-        .warn(Color::Yellow)
-        .info(Color::Green)
-        .debug(Color::Blue)
-        .trace(Color::Magenta);
-
-    // Configure the logger
-    let dispatch = Dispatch::new()
-        .format(move |out, message, record| {
-            // Generate the current timestamp
-            let timestamp = Local::now().format(timestamp_format);
-
-            // Format output with or without colour based on `use_colour`
-            if use_colour {
-                out.finish(format_args!(
-                    "[{} {} {}] {}",
-                    timestamp,
-                    colours.color(record.level()),
-                    record.target(),
-                    message
-                ))
-            } else {
+    // Apply the logger configuration
+    create_dispatch(log_level, log_format, use_colour, log_file_path)?.apply()?;
+    LOGGER_INITIALISED.store(true, Ordering::Relaxed);
+
+    Ok(())
+}
+
+/// Whether the program logger has already been initialised with [`init`]
+pub fn is_logger_initialised() -> bool {
+    LOGGER_INITIALISED.load(Ordering::Relaxed)
+}
+
+/// Create the logger configuration, writing to stdout and optionally to a file.
+///
+/// # Arguments
+///
+/// * `log_level`: The maximum level of records to write
+/// * `log_format`: The format in which to write log records
+/// * `use_colour`: Whether to colourise text output to stdout
+/// * `log_file_path`: If provided, records are also written to this file (without colour)
+fn create_dispatch(
+    log_level: LevelFilter,
+    log_format: LogFormat,
+    use_colour: bool,
+    log_file_path: Option<&Path>,
+) -> Result<Dispatch> {
+    let mut dispatch = Dispatch::new().level(log_level).chain(
+        Dispatch::new()
+            .format(move |out, message, record| {
                 out.finish(format_args!(
-                    "[{} {} {}] {}",
-                    timestamp,
-                    record.level(),
-                    record.target(),
-                    message
+                    "{}",
+                    format_record(message, record, log_format, use_colour)
                 ))
-            }
-        })
-        .level(log_level)
-        .chain(std::io::stdout());
+            })
+            .chain(std::io::stdout()),
+    );
 
-    // Apply the logger configuration
-    dispatch.apply()?;
+    if let Some(log_file_path) = log_file_path {
+        let file = File::create(log_file_path)
+            .with_context(|| format!("Could not create log file {}", log_file_path.display()))?;
+        dispatch = dispatch.chain(
+            Dispatch::new()
+                .format(move |out, message, record| {
+                    out.finish(format_args!(
+                        "{}",
+                        format_record(message, record, log_format, false)
+                    ))
+                })
+                .chain(file),
+        );
+    }
 
-    Ok(())
+    Ok(dispatch)
+}
+
+/// Format a log record in the specified format.
+///
+/// # Arguments
+///
+/// * `message`: The log message
+/// * `record`: The log record
+/// * `log_format`: The format in which to write the record
+/// * `use_colour`: Whether to colourise the log level (text format only)
+fn format_record(
+    message: &Arguments,
+    record: &Record,
+    log_format: LogFormat,
+    use_colour: bool,
+) -> String {
+    if log_format == LogFormat::Json {
+        return format_json_record(
+            &Local::now().to_rfc3339(),
+            record.level(),
+            record.target(),
+            &message.to_string(),
+        );
+    }
+
+    // Format timestamp as HH:MM:SS
+    let timestamp = Local::now().format("%H:%M:%S");
+
+    // Format output with or without colour based on `use_colour`
+    if use_colour {
+        // Set up colours for log levels
+        let colours = ColoredLevelConfig::new()
+            .error(Color::Red)
+            .warn(Color::Yellow)
+            .info(Color::Green)
+            .debug(Color::Blue)
+            .trace(Color::Magenta);
+
+        format!(
+            "[{} {} {}] {}",
+            timestamp,
+            colours.color(record.level()),
+            record.target(),
+            message
+        )
+    } else {
+        format!(
+            "[{} {} {}] {}",
+            timestamp,
+            record.level(),
+            record.target(),
+            message
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::fs;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_create_dispatch_log_file() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("run.log");
+        let (_, logger) =
+            create_dispatch(LevelFilter::Info, LogFormat::Text, false, Some(&file_path))
+                .unwrap()
+                .into_log();
+        assert!(file_path.is_file());
+
+        for (level, message) in [(Level::Info, "Hello"), (Level::Debug, "Too verbose")] {
+            logger.log(
+                &Record::builder()
+                    .args(format_args!("{message}"))
+                    .level(level)
+                    .target("muse2")
+                    .build(),
+            );
+        }
+        logger.flush();
+
+        let contents = fs::read_to_string(&file_path).unwrap();
+        assert!(contents.contains("INFO muse2] Hello"));
+        assert!(!contents.contains("Too verbose"));
+    }
+
+    #[test]
+    fn test_si_value() {
+        assert_eq!(SIValue(1234567890.0).to_string(), "1.23G");
+        assert_eq!(SIValue(2.5e12).to_string(), "2.50T");
+        assert_eq!(SIValue(-2500000.0).to_string(), "-2.50M");
+        assert_eq!(SIValue(1000.0).to_string(), "1.00k");
+        assert_eq!(format!("{:.1}", SIValue(4002.26)), "4.0k");
+
+        // Small values have no suffix
+        assert_eq!(SIValue(999.5).to_string(), "999.5");
+        assert_eq!(SIValue(0.0).to_string(), "0");
+        assert_eq!(format!("{:.2}", SIValue(3.964844)), "3.96");
+
+        // Non-finite values
+        assert_eq!(SIValue(f64::INFINITY).to_string(), "inf");
+        assert_eq!(SIValue(f64::NAN).to_string(), "NaN");
+    }
+
+    #[test]
+    fn test_format_json_record() {
+        let record = format_json_record(
+            "2025-01-01T12:00:00+00:00",
+            Level::Warn,
+            "muse2::input",
+            "Something \"odd\" happened",
+        );
+        let value: Value = serde_json::from_str(&record).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "timestamp": "2025-01-01T12:00:00+00:00",
+                "level": "WARN",
+                "target": "muse2::input",
+                "message": "Something \"odd\" happened",
+            })
+        );
+
+        // Each record is written on a single line
+        assert!(!record.contains('\n'));
+    }
 }
@@ -2,16 +2,35 @@ use clap::Parser;
 use muse2::commands;
 
 use commands::{
-    handle_example_list_command, handle_run_command, Cli, Commands, ExampleSubcommands,
+    handle_example_list_command, handle_run_command, handle_validate_command, Cli, Commands,
+    ExampleSubcommands,
 };
 
 fn main() {
     let cli = Cli::parse();
     match cli.command {
-        Commands::Run { model_dir } => handle_run_command(&model_dir),
+        Commands::Run {
+            model_dir,
+            output_dir,
+            years,
+            checkpoint,
+            overrides,
+            log_format,
+        } => handle_run_command(
+            &model_dir,
+            output_dir.as_deref(),
+            years,
+            checkpoint.as_deref(),
+            &overrides,
+            log_format,
+        ),
+        Commands::Validate { model_dir } => handle_validate_command(&model_dir),
         Commands::Example { subcommand } => match subcommand {
             ExampleSubcommands::List => handle_example_list_command(),
         },
     }
-    .unwrap_or_else(|err| eprintln!("{:?}", err))
+    .unwrap_or_else(|err| {
+        eprintln!("{:?}", err);
+        std::process::exit(1);
+    })
 }
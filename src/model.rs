@@ -7,8 +7,10 @@ use crate::process::Process;
 use crate::region::Region;
 use crate::time_slice::TimeSliceInfo;
 use anyhow::{ensure, Context, Result};
+use itertools::Itertools;
 use serde::Deserialize;
 use std::collections::HashMap;
+use std::fmt::Display;
 use std::path::Path;
 use std::rc::Rc;
 
@@ -24,6 +26,33 @@ pub struct Model {
     pub regions: HashMap<Rc<str>, Region>,
 }
 
+/// Counts of the entities in a [`Model`]
+#[derive(Debug, PartialEq)]
+pub struct ModelSummary {
+    pub num_regions: usize,
+    pub num_commodities: usize,
+    pub num_processes: usize,
+    pub num_agents: usize,
+    pub num_milestone_years: usize,
+    pub num_time_slices: usize,
+}
+
+impl Display for ModelSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} regions, {} commodities, {} processes, {} agents, {} milestone years, \
+            {} time slices",
+            self.num_regions,
+            self.num_commodities,
+            self.num_processes,
+            self.num_agents,
+            self.num_milestone_years,
+            self.num_time_slices
+        )
+    }
+}
+
 /// Represents the contents of the entire model file.
 #[derive(Debug, Deserialize, PartialEq)]
 pub struct ModelFile {
@@ -80,20 +109,85 @@ impl ModelFile {
 }
 
 impl Model {
+    /// Get a summary of the number of entities in the model.
+    pub fn summary(&self) -> ModelSummary {
+        ModelSummary {
+            num_regions: self.regions.len(),
+            num_commodities: self.commodities.len(),
+            num_processes: self.processes.len(),
+            num_agents: self.agents.len(),
+            num_milestone_years: self.milestone_years.len(),
+            num_time_slices: self.time_slice_info.fractions.len(),
+        }
+    }
+
     /// Iterate over the model's milestone years.
     pub fn iter_years(&self) -> impl Iterator<Item = u32> + '_ {
         self.milestone_years.iter().copied()
     }
 
     /// Iterate over the model's regions (region IDs).
+    ///
+    /// Regions are returned in order of ID, so that the order is the same between runs.
     pub fn iter_regions(&self) -> impl Iterator<Item = &Rc<str>> + '_ {
-        self.regions.keys()
+        self.regions.keys().sorted()
+    }
+
+    /// Iterate over the model's agents.
+    ///
+    /// Agents are returned in order of ID, so that the order is the same between runs.
+    pub fn iter_agents(&self) -> impl Iterator<Item = &Agent> + '_ {
+        self.agents
+            .iter()
+            .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+            .map(|(_, agent)| agent)
+    }
+
+    /// Check that every process has an availability defined for every time slice.
+    ///
+    /// Coverage of time slices by demand slices is already checked when demand is read.
+    ///
+    /// # Returns
+    ///
+    /// An error naming the first process (by ID) with uncovered time slices, if any.
+    pub fn validate_time_slice_coverage(&self) -> Result<()> {
+        for (id, process) in self
+            .processes
+            .iter()
+            .sorted_by(|(id1, _), (id2, _)| id1.cmp(id2))
+        {
+            let missing = self
+                .time_slice_info
+                .iter_ids()
+                .filter(|time_slice| {
+                    !process
+                        .availabilities
+                        .iter()
+                        .any(|availability| availability.time_slice.contains(time_slice))
+                })
+                .map(|time_slice| time_slice.to_string())
+                .sorted()
+                .collect_vec();
+            ensure!(
+                missing.is_empty(),
+                "Process {id} has no availability defined for time slices: {}",
+                missing.join(", ")
+            );
+        }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agent::{DecisionRule, SearchSpace};
+    use crate::fixture::{create_commodity, create_process_parameter};
+    use crate::process::{LimitType, ProcessAvailability};
+    use crate::region::RegionSelection;
+    use crate::time_slice::{TimeSliceID, TimeSliceSelection};
+    use std::collections::HashSet;
     use std::fs::File;
     use std::io::Write;
     use tempfile::tempdir;
@@ -121,4 +215,147 @@ mod tests {
         let model_file = ModelFile::from_path(dir.path()).unwrap();
         assert_eq!(model_file.milestone_years.years, vec![2020, 2100]);
     }
+
+    /// Create a model with the specified regions and agents and no other data
+    fn create_model(region_ids: &[&str], agent_ids: &[&str]) -> Model {
+        let regions = region_ids
+            .iter()
+            .map(|id| {
+                let region = Region {
+                    id: (*id).into(),
+                    description: "A region".into(),
+                    neighbours: HashSet::new(),
+                };
+                (Rc::clone(&region.id), region)
+            })
+            .collect();
+        let commodity = Rc::new(create_commodity("commodity1"));
+        let agents = agent_ids
+            .iter()
+            .map(|id| {
+                let agent = Agent {
+                    id: (*id).into(),
+                    description: "".into(),
+                    commodity: Rc::clone(&commodity),
+                    commodity_portion: 1.0,
+                    search_space: SearchSpace::AllProcesses,
+                    decision_rule: DecisionRule::Single,
+                    capex_limit: None,
+                    annual_cost_limit: None,
+                    regions: RegionSelection::All,
+                    objectives: Vec::new(),
+                };
+                (Rc::clone(&agent.id), agent)
+            })
+            .collect();
+
+        Model {
+            milestone_years: vec![2020, 2030],
+            agents,
+            commodities: HashMap::new(),
+            processes: HashMap::new(),
+            time_slice_info: TimeSliceInfo::default(),
+            regions,
+        }
+    }
+
+    #[test]
+    fn test_model_validate_time_slice_coverage() {
+        let time_slices = ["winter", "summer"].map(|season| TimeSliceID {
+            season: season.into(),
+            time_of_day: "day".into(),
+        });
+        let time_slice_info = TimeSliceInfo {
+            seasons: ["winter".into(), "summer".into()].into_iter().collect(),
+            times_of_day: ["day".into()].into_iter().collect(),
+            fractions: time_slices.iter().map(|ts| (ts.clone(), 0.5)).collect(),
+        };
+        let create_process = |selections: Vec<TimeSliceSelection>| Process {
+            id: "process1".into(),
+            description: "Description".into(),
+            availabilities: selections
+                .into_iter()
+                .map(|time_slice| ProcessAvailability {
+                    process_id: "process1".into(),
+                    limit_type: LimitType::UpperBound,
+                    time_slice,
+                    value: 1.0,
+                })
+                .collect(),
+            flows: vec![],
+            parameter: create_process_parameter("process1"),
+            regions: RegionSelection::All,
+        };
+        let mut model = Model {
+            time_slice_info,
+            ..create_model(&["GBR"], &[])
+        };
+
+        // Covered
+        for selections in [
+            vec![TimeSliceSelection::Annual],
+            vec![
+                TimeSliceSelection::Season("winter".into()),
+                TimeSliceSelection::Single(time_slices[1].clone()),
+            ],
+        ] {
+            let process = create_process(selections);
+            model.processes = [(Rc::clone(&process.id), process.into())]
+                .into_iter()
+                .collect();
+            assert!(model.validate_time_slice_coverage().is_ok());
+        }
+
+        // Partly uncovered
+        let process = create_process(vec![TimeSliceSelection::Season("winter".into())]);
+        model.processes = [(Rc::clone(&process.id), process.into())]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            model
+                .validate_time_slice_coverage()
+                .unwrap_err()
+                .to_string(),
+            "Process process1 has no availability defined for time slices: summer.day"
+        );
+    }
+
+    #[test]
+    fn test_model_summary() {
+        let model = create_model(&["GBR", "FRA"], &["agent1", "agent2", "agent3"]);
+        let summary = model.summary();
+        assert_eq!(
+            summary,
+            ModelSummary {
+                num_regions: 2,
+                num_commodities: 0,
+                num_processes: 0,
+                num_agents: 3,
+                num_milestone_years: 2,
+                num_time_slices: 1,
+            }
+        );
+        assert_eq!(
+            summary.to_string(),
+            "2 regions, 0 commodities, 0 processes, 3 agents, 2 milestone years, 1 time slices"
+        );
+    }
+
+    #[test]
+    fn test_model_iter_regions_and_agents_sorted() {
+        let model = create_model(
+            &["GBR", "FRA", "DEU", "ESP", "ITA"],
+            &["agent3", "agent1", "agent5", "agent2", "agent4"],
+        );
+
+        // Order is by ID and so is the same every time
+        assert!(model
+            .iter_regions()
+            .map(|id| id.as_ref())
+            .eq(["DEU", "ESP", "FRA", "GBR", "ITA"]));
+        assert!(model
+            .iter_agents()
+            .map(|agent| agent.id.as_ref())
+            .eq(["agent1", "agent2", "agent3", "agent4", "agent5"]));
+    }
 }
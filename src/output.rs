@@ -0,0 +1,270 @@
+//! Code for writing simulation results to output files.
+use crate::agent::Asset;
+use anyhow::{Context, Result};
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+/// The root folder in which model-specific output folders will be created
+const OUTPUT_DIRECTORY_ROOT: &str = "muse2_results";
+
+const CAPACITY_FILE_NAME: &str = "capacity.csv";
+const DECOMMISSIONS_FILE_NAME: &str = "decommissions.csv";
+
+/// Get the output folder for the specified model, creating it if it doesn't exist.
+///
+/// The folder is named after the model directory and placed inside a `muse2_results` folder in the
+/// current working directory.
+///
+/// # Arguments
+///
+/// * `model_dir` - Folder containing model configuration files
+pub fn get_output_dir(model_dir: &Path) -> Result<PathBuf> {
+    // Canonicalise in case the user has specified "."
+    let model_dir = model_dir
+        .canonicalize()
+        .with_context(|| format!("Invalid model directory: {}", model_dir.display()))?;
+    let model_name = model_dir
+        .file_name()
+        .context("Model directory cannot be the root folder")?;
+    let output_dir = Path::new(OUTPUT_DIRECTORY_ROOT).join(model_name);
+    create_output_dir(&output_dir)?;
+
+    Ok(output_dir)
+}
+
+/// Create the specified output folder (and any parent folders) if it doesn't exist.
+///
+/// # Arguments
+///
+/// * `output_dir` - The output folder
+pub fn create_output_dir(output_dir: &Path) -> Result<()> {
+    fs::create_dir_all(output_dir)
+        .with_context(|| format!("Could not create output folder {}", output_dir.display()))
+}
+
+/// Represents a row of the capacity CSV file
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct CapacityRow {
+    milestone_year: u32,
+    region_id: Rc<str>,
+    process_id: Rc<str>,
+    agent_id: Rc<str>,
+    capacity: f64,
+}
+
+/// Represents a row of the decommissions CSV file
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+struct DecommissionRow {
+    milestone_year: u32,
+    agent_id: Rc<str>,
+    process_id: Rc<str>,
+    region_id: Rc<str>,
+    capacity: f64,
+    commission_year: u32,
+    decommission_year: u32,
+}
+
+/// Writes simulation results to CSV files in an output folder
+pub struct DataWriter {
+    capacity_writer: csv::Writer<File>,
+    decommissions_writer: csv::Writer<File>,
+}
+
+impl DataWriter {
+    /// Create a new [`DataWriter`], creating output files in the specified folder.
+    ///
+    /// # Arguments
+    ///
+    /// * `output_path` - Folder in which to create output files
+    pub fn create(output_path: &Path) -> Result<Self> {
+        let create_writer = |file_name| {
+            let file_path = output_path.join(file_name);
+            csv::Writer::from_path(&file_path)
+                .with_context(|| format!("Could not create {}", file_path.display()))
+        };
+
+        Ok(Self {
+            capacity_writer: create_writer(CAPACITY_FILE_NAME)?,
+            decommissions_writer: create_writer(DECOMMISSIONS_FILE_NAME)?,
+        })
+    }
+
+    /// Write the installed capacity for a milestone year.
+    ///
+    /// The capacities of assets with the same region, process and agent are summed to give a
+    /// single row.
+    ///
+    /// # Arguments
+    ///
+    /// * `milestone_year` - The current milestone year
+    /// * `assets` - The assets to include
+    pub fn write_capacity<'a, I>(&mut self, milestone_year: u32, assets: I) -> Result<()>
+    where
+        I: Iterator<Item = &'a Asset>,
+    {
+        let mut capacities = HashMap::new();
+        for asset in assets {
+            let key = (
+                Rc::clone(&asset.region_id),
+                Rc::clone(&asset.process.id),
+                Rc::clone(&asset.agent_id),
+            );
+            *capacities.entry(key).or_insert(0.0) += asset.capacity;
+        }
+
+        // Sort rows so that output is deterministic
+        for ((region_id, process_id, agent_id), capacity) in capacities
+            .into_iter()
+            .sorted_by(|(key1, _), (key2, _)| key1.cmp(key2))
+        {
+            let row = CapacityRow {
+                milestone_year,
+                region_id,
+                process_id,
+                agent_id,
+                capacity,
+            };
+            self.capacity_writer.serialize(row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Write the assets which were decommissioned in a milestone year.
+    ///
+    /// Each asset is written as a single row, including the capacity it had at retirement.
+    ///
+    /// # Arguments
+    ///
+    /// * `milestone_year` - The current milestone year
+    /// * `assets` - The assets decommissioned in this milestone year
+    pub fn write_decommissions<'a, I>(&mut self, milestone_year: u32, assets: I) -> Result<()>
+    where
+        I: Iterator<Item = &'a Asset>,
+    {
+        for asset in assets {
+            let row = DecommissionRow {
+                milestone_year,
+                agent_id: Rc::clone(&asset.agent_id),
+                process_id: Rc::clone(&asset.process.id),
+                region_id: Rc::clone(&asset.region_id),
+                capacity: asset.capacity,
+                commission_year: asset.commission_year,
+                decommission_year: asset.decommission_year(),
+            };
+            self.decommissions_writer.serialize(row)?;
+        }
+
+        Ok(())
+    }
+
+    /// Flush the underlying streams
+    pub fn flush(&mut self) -> Result<()> {
+        self.capacity_writer.flush()?;
+        self.decommissions_writer.flush()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::create_process;
+    use crate::process::Process;
+    use tempfile::tempdir;
+
+    fn create_asset(process: &Rc<Process>, capacity: f64) -> Asset {
+        Asset {
+            id: None,
+            agent_id: "agent1".into(),
+            process: Rc::clone(process),
+            region_id: "GBR".into(),
+            capacity,
+            commission_year: 2010,
+            mothball_year: None,
+        }
+    }
+
+    #[test]
+    fn test_write_capacity() {
+        let process = create_process("process1");
+        let assets = [create_asset(&process, 1.0), create_asset(&process, 2.0)];
+
+        let dir = tempdir().unwrap();
+        {
+            let mut writer = DataWriter::create(dir.path()).unwrap();
+            writer.write_capacity(2020, assets.iter()).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let rows: Vec<CapacityRow> = csv::Reader::from_path(dir.path().join(CAPACITY_FILE_NAME))
+            .unwrap()
+            .into_deserialize()
+            .try_collect()
+            .unwrap();
+        assert_eq!(
+            rows,
+            [CapacityRow {
+                milestone_year: 2020,
+                region_id: "GBR".into(),
+                process_id: "process1".into(),
+                agent_id: "agent1".into(),
+                capacity: 3.0,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_write_decommissions() {
+        let process = create_process("process1");
+        let assets = [
+            create_asset(&process, 1.0),
+            Asset {
+                commission_year: 2015,
+                ..create_asset(&process, 2.0)
+            },
+        ];
+
+        let dir = tempdir().unwrap();
+        {
+            let mut writer = DataWriter::create(dir.path()).unwrap();
+            writer.write_decommissions(2030, assets.iter()).unwrap();
+            writer.flush().unwrap();
+        }
+
+        let rows: Vec<DecommissionRow> =
+            csv::Reader::from_path(dir.path().join(DECOMMISSIONS_FILE_NAME))
+                .unwrap()
+                .into_deserialize()
+                .try_collect()
+                .unwrap();
+        assert_eq!(
+            rows,
+            [
+                DecommissionRow {
+                    milestone_year: 2030,
+                    agent_id: "agent1".into(),
+                    process_id: "process1".into(),
+                    region_id: "GBR".into(),
+                    capacity: 1.0,
+                    commission_year: 2010,
+                    decommission_year: 2020,
+                },
+                DecommissionRow {
+                    milestone_year: 2030,
+                    agent_id: "agent1".into(),
+                    process_id: "process1".into(),
+                    region_id: "GBR".into(),
+                    capacity: 2.0,
+                    commission_year: 2015,
+                    decommission_year: 2025,
+                },
+            ]
+        );
+    }
+}
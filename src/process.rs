@@ -4,6 +4,7 @@ use crate::region::RegionSelection;
 use crate::time_slice::TimeSliceSelection;
 use serde::Deserialize;
 use serde_string_enum::DeserializeLabeledStringEnum;
+use std::collections::HashSet;
 use std::ops::RangeInclusive;
 use std::rc::Rc;
 
@@ -22,6 +23,41 @@ impl Process {
     pub fn iter_pacs(&self) -> impl Iterator<Item = &ProcessFlow> {
         self.flows.iter().filter(|flow| flow.is_pac)
     }
+
+    /// Get the capacity to activity conversion factor for the specified region and year.
+    ///
+    /// # Returns
+    ///
+    /// The conversion factor, or `None` if the process does not operate in this region and year.
+    pub fn capacity_to_activity_for(&self, region_id: &str, year: u32) -> Option<f64> {
+        (self.regions.contains(region_id) && self.parameter.years.contains(&year))
+            .then_some(self.parameter.cap2act)
+    }
+
+    /// Get the IDs of the commodities in the same flexible group as the specified commodity.
+    ///
+    /// A flexible group consists of all the flexible flows of a process in the same direction (i.e.
+    /// all inputs or all outputs). The returned set includes `commodity_id` itself.
+    ///
+    /// # Returns
+    ///
+    /// The set of commodity IDs in the group, or `None` if the process has no flexible flow for
+    /// `commodity_id`.
+    pub fn get_flexible_group(&self, commodity_id: &str) -> Option<HashSet<Rc<str>>> {
+        let flow = self
+            .flows
+            .iter()
+            .find(|flow| flow.is_flexible() && flow.commodity.id.as_ref() == commodity_id)?;
+        let is_output = flow.is_output();
+
+        Some(
+            self.flows
+                .iter()
+                .filter(|other| other.is_flexible() && other.is_output() == is_output)
+                .map(|other| Rc::clone(&other.commodity.id))
+                .collect(),
+        )
+    }
 }
 
 /// The availabilities for a process over time slices
@@ -37,7 +73,7 @@ pub struct ProcessAvailability {
     pub value: f64,
 }
 
-#[derive(PartialEq, Debug, DeserializeLabeledStringEnum)]
+#[derive(PartialEq, Clone, Debug, DeserializeLabeledStringEnum)]
 pub enum LimitType {
     #[string = "lo"]
     LowerBound,
@@ -69,6 +105,18 @@ pub struct ProcessFlow {
     pub is_pac: bool,
 }
 
+impl ProcessFlow {
+    /// Whether this flow is flexible
+    pub fn is_flexible(&self) -> bool {
+        self.flow_type == FlowType::Flexible
+    }
+
+    /// Whether this flow is an output (rather than an input)
+    pub fn is_output(&self) -> bool {
+        self.flow > 0.0
+    }
+}
+
 #[derive(PartialEq, Default, Debug, Clone, DeserializeLabeledStringEnum)]
 pub enum FlowType {
     #[default]
@@ -92,3 +140,73 @@ pub struct ProcessParameter {
     pub discount_rate: f64,
     pub cap2act: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixture::{create_commodity, create_process_parameter};
+
+    fn create_flow(commodity_id: &str, flow: f64, flow_type: FlowType) -> ProcessFlow {
+        let commodity = create_commodity(commodity_id);
+
+        ProcessFlow {
+            process_id: "process1".into(),
+            commodity: Rc::new(commodity),
+            flow,
+            flow_type,
+            flow_cost: 0.0,
+            is_pac: false,
+        }
+    }
+
+    fn create_process(flows: Vec<ProcessFlow>, regions: RegionSelection) -> Process {
+        Process {
+            id: "process1".into(),
+            description: "Description".into(),
+            availabilities: vec![],
+            flows,
+            parameter: ProcessParameter {
+                cap2act: 3.0,
+                ..create_process_parameter("process1")
+            },
+            regions,
+        }
+    }
+
+    #[test]
+    fn test_capacity_to_activity_for() {
+        let regions = RegionSelection::Some(["GBR".into()].into_iter().collect());
+        let process = create_process(vec![], regions);
+        assert_eq!(process.capacity_to_activity_for("GBR", 2010), Some(3.0));
+        assert_eq!(process.capacity_to_activity_for("GBR", 2020), Some(3.0));
+
+        // Region or year not covered by process
+        assert!(process.capacity_to_activity_for("FRA", 2010).is_none());
+        assert!(process.capacity_to_activity_for("GBR", 2021).is_none());
+    }
+
+    #[test]
+    fn test_get_flexible_group() {
+        let flows = vec![
+            create_flow("GAS", -1.0, FlowType::Flexible),
+            create_flow("OIL", -1.0, FlowType::Flexible),
+            create_flow("COAL", -1.0, FlowType::Fixed),
+            create_flow("ELC", 1.0, FlowType::Flexible),
+            create_flow("HEAT", 1.0, FlowType::Fixed),
+        ];
+        let process = create_process(flows, RegionSelection::All);
+
+        let inputs: HashSet<Rc<str>> = ["GAS".into(), "OIL".into()].into_iter().collect();
+        assert_eq!(process.get_flexible_group("GAS").unwrap(), inputs);
+        assert_eq!(process.get_flexible_group("OIL").unwrap(), inputs);
+        assert_eq!(
+            process.get_flexible_group("ELC").unwrap(),
+            ["ELC".into()].into_iter().collect()
+        );
+
+        // Fixed flows and unknown commodities don't belong to a flexible group
+        assert!(process.get_flexible_group("COAL").is_none());
+        assert!(process.get_flexible_group("HEAT").is_none());
+        assert!(process.get_flexible_group("NONEXISTENT").is_none());
+    }
+}
@@ -10,6 +10,16 @@ pub struct Region {
     pub id: Rc<str>,
     /// A text description of the region (e.g. "United Kingdom").
     pub description: String,
+    /// The IDs of regions adjacent to this one
+    #[serde(skip)]
+    pub neighbours: HashSet<Rc<str>>,
+}
+
+impl Region {
+    /// Whether the specified region is adjacent to this one
+    pub fn is_neighbour(&self, region_id: &str) -> bool {
+        self.neighbours.contains(region_id)
+    }
 }
 
 /// Represents multiple regions
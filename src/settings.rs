@@ -1,16 +1,64 @@
 //! Code for loading program settings.
-use crate::input::read_toml;
-use anyhow::Result;
+use crate::input::{input_err_msg, read_toml};
+use anyhow::{bail, ensure, Context, Result};
 use serde::Deserialize;
 use std::path::Path;
+use std::str::FromStr;
 
 const SETTINGS_FILE_NAME: &str = "settings.toml";
 
+/// The default tolerance used when checking that demand slicing fractions sum to one
+const DEFAULT_DEMAND_SLICING_TOLERANCE: f64 = 1e-5;
+
+/// The default seed for random number generation, so that runs are reproducible by default
+const DEFAULT_RANDOM_SEED: u64 = 42;
+
 /// Program settings from config file
-#[derive(Debug, Default, Deserialize, PartialEq)]
+#[derive(Debug, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
 pub struct Settings {
     /// The user's preferred logging level
     pub log_level: Option<String>,
+    /// Whether to linearly interpolate demand onto milestone years, rather than requiring demand to
+    /// be given for milestone years only
+    pub interpolate_demand: bool,
+    /// The tolerance used when checking that demand slicing fractions sum to one
+    pub demand_slicing_tolerance: f64,
+    /// Whether to distribute process availabilities given for a season across its time slices, in
+    /// proportion to their duration
+    pub distribute_seasonal_availability: bool,
+    /// Whether to report all errors found in input files together, rather than stopping at the
+    /// first one
+    pub collect_all_errors: bool,
+    /// Whether it is an error, rather than a warning, for an agent to have no processes which can
+    /// produce its commodity
+    pub error_on_missing_producers: bool,
+    /// Whether to ignore rows beginning with `#` in the demand and process flow input files
+    pub allow_csv_comments: bool,
+    /// The seed used for random number generation
+    pub random_seed: u64,
+    /// Whether it is an error for a commodity to have costs for some but not all milestone years in
+    /// a region
+    pub strict_levies: bool,
+    /// Whether to write log output to a file in the output folder, as well as to the terminal
+    pub log_to_file: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            log_level: None,
+            interpolate_demand: false,
+            demand_slicing_tolerance: DEFAULT_DEMAND_SLICING_TOLERANCE,
+            distribute_seasonal_availability: false,
+            collect_all_errors: false,
+            error_on_missing_producers: false,
+            allow_csv_comments: false,
+            random_seed: DEFAULT_RANDOM_SEED,
+            strict_levies: true,
+            log_to_file: true,
+        }
+    }
 }
 
 impl Settings {
@@ -31,7 +79,60 @@ impl Settings {
             return Ok(Settings::default());
         }
 
-        read_toml(&file_path)
+        let settings: Settings = read_toml(&file_path)?;
+        settings
+            .validate()
+            .with_context(|| input_err_msg(&file_path))?;
+
+        Ok(settings)
+    }
+
+    /// Check that the values of settings are within their acceptable ranges
+    fn validate(&self) -> Result<()> {
+        ensure!(
+            self.demand_slicing_tolerance > 0.0 && self.demand_slicing_tolerance < 1.0,
+            "demand_slicing_tolerance must be greater than 0 and less than 1 (got {})",
+            self.demand_slicing_tolerance
+        );
+
+        Ok(())
+    }
+
+    /// Override the value of a setting.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The name of the setting
+    /// * `value` - The new value, as a string
+    ///
+    /// # Returns
+    ///
+    /// An error if `key` is not a known setting or `value` cannot be parsed or is out of range
+    pub fn set(&mut self, key: &str, value: &str) -> Result<()> {
+        fn parse<T: FromStr>(key: &str, value: &str) -> Result<T> {
+            value
+                .parse()
+                .ok()
+                .with_context(|| format!("Invalid value for setting {key}: {value}"))
+        }
+
+        match key {
+            "log_level" => self.log_level = Some(value.to_string()),
+            "interpolate_demand" => self.interpolate_demand = parse(key, value)?,
+            "demand_slicing_tolerance" => self.demand_slicing_tolerance = parse(key, value)?,
+            "distribute_seasonal_availability" => {
+                self.distribute_seasonal_availability = parse(key, value)?
+            }
+            "collect_all_errors" => self.collect_all_errors = parse(key, value)?,
+            "error_on_missing_producers" => self.error_on_missing_producers = parse(key, value)?,
+            "allow_csv_comments" => self.allow_csv_comments = parse(key, value)?,
+            "random_seed" => self.random_seed = parse(key, value)?,
+            "strict_levies" => self.strict_levies = parse(key, value)?,
+            "log_to_file" => self.log_to_file = parse(key, value)?,
+            _ => bail!("Unknown setting: {key}"),
+        }
+
+        self.validate()
     }
 }
 
@@ -61,8 +162,57 @@ mod tests {
         assert_eq!(
             Settings::from_path(dir.path()).unwrap(),
             Settings {
-                log_level: Some("warn".to_string())
+                log_level: Some("warn".to_string()),
+                ..Settings::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_settings_from_path_invalid() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(SETTINGS_FILE_NAME);
+
+        // Unknown key
+        {
+            let mut file = File::create(&file_path).unwrap();
+            writeln!(file, "interpolate_demnd = true").unwrap();
+        }
+        let err = Settings::from_path(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("unknown field `interpolate_demnd`"));
+
+        // Out-of-range value
+        {
+            let mut file = File::create(&file_path).unwrap();
+            writeln!(file, "demand_slicing_tolerance = -0.1").unwrap();
+        }
+        let err = Settings::from_path(dir.path()).unwrap_err();
+        assert!(format!("{err:#}").contains("demand_slicing_tolerance must be greater than 0"));
+    }
+
+    #[test]
+    fn test_settings_set() {
+        let mut settings = Settings::default();
+        settings.set("interpolate_demand", "true").unwrap();
+        settings.set("demand_slicing_tolerance", "1e-3").unwrap();
+        settings.set("log_level", "warn").unwrap();
+        assert_eq!(
+            settings,
+            Settings {
+                log_level: Some("warn".to_string()),
+                interpolate_demand: true,
+                demand_slicing_tolerance: 1e-3,
+                ..Settings::default()
             }
         );
+
+        // Unknown key
+        assert!(settings.set("not_a_setting", "1").is_err());
+
+        // Unparseable value
+        assert!(settings.set("interpolate_demand", "maybe").is_err());
+
+        // Out-of-range value
+        assert!(settings.set("demand_slicing_tolerance", "0").is_err());
     }
 }
@@ -1,19 +1,12 @@
 //! Functionality for running the MUSE 2.0 simulation.
-use crate::agent::{Asset, AssetPool};
+use crate::agent::AssetPool;
+use crate::log::SIValue;
 use crate::model::Model;
+use crate::output::DataWriter;
+use anyhow::Result;
+use itertools::Itertools;
 use log::info;
-use std::rc::Rc;
-
-/// Get an iterator of active [`Asset`]s for the specified milestone year in a given region.
-fn filter_assets<'a>(
-    assets: &'a AssetPool,
-    year: u32,
-    region_id: &'a Rc<str>,
-) -> impl Iterator<Item = &'a Asset> {
-    assets
-        .iter()
-        .filter(move |asset| asset.commission_year >= year && asset.region_id == *region_id)
-}
+use std::time::{Duration, Instant};
 
 /// Run the simulation.
 ///
@@ -21,21 +14,143 @@ fn filter_assets<'a>(
 ///
 /// * `model` - The model to run
 /// * `assets` - The asset pool
-pub fn run(model: &Model, assets: &AssetPool) {
-    for year in model.iter_years() {
-        info!("Milestone year: {year}");
+/// * `writer` - For writing results to output files
+pub fn run(model: &Model, assets: &mut AssetPool, writer: &mut DataWriter) -> Result<()> {
+    let num_years = model.milestone_years.len();
+    let start = Instant::now();
+    let mut year_durations = Vec::with_capacity(num_years);
+    for (i, year) in model.iter_years().enumerate() {
+        let year_start = Instant::now();
+        let eta = estimate_time_remaining(&year_durations, num_years - i)
+            .map(|eta| format!("{eta:.1?}"))
+            .unwrap_or_else(|| "unknown".into());
+        info!(
+            "Milestone year: {year} ({}/{num_years}, elapsed: {:.1?}, ETA: {eta})",
+            i + 1,
+            start.elapsed()
+        );
+        let new_ids = assets.commission_new(year);
+        if !new_ids.is_empty() {
+            info!(
+                "Commissioned assets: {}",
+                new_ids.iter().map(|id| id.to_string()).join(", ")
+            );
+        }
+        let decommissioned = assets.decommission_old(year);
+        writer.write_decommissions(year, decommissioned.iter())?;
+        writer.write_capacity(year, assets.iter_active())?;
+
+        // Regions and agents are visited in order of ID, so the log is the same between runs
         for region_id in model.iter_regions() {
             info!("├── Region: {region_id}");
-            for asset in filter_assets(assets, year, region_id) {
-                info!(
-                    "│   ├── Agent {} has asset {} (commissioned in {})",
-                    asset.agent_id, asset.process.id, asset.commission_year
-                );
-
-                for flow in asset.process.flows.iter() {
-                    info!("│   │   ├── Commodity: {}", flow.commodity.id);
+            for agent in model.iter_agents() {
+                for asset in assets
+                    .iter_active()
+                    .filter(|asset| asset.region_id == *region_id && asset.agent_id == agent.id)
+                {
+                    info!(
+                        "│   ├── Agent {} has asset {} (commissioned in {}, capacity: {})",
+                        agent.id,
+                        asset.process.id,
+                        asset.commission_year,
+                        SIValue(asset.capacity)
+                    );
+
+                    for flow in asset.process.flows.iter() {
+                        info!("│   │   ├── Commodity: {}", flow.commodity.id);
+                    }
                 }
             }
         }
+
+        year_durations.push(year_start.elapsed());
+    }
+
+    writer.flush()
+}
+
+/// Estimate the time remaining for the simulation.
+///
+/// # Arguments
+///
+/// * `year_durations` - How long each milestone year completed so far took to run
+/// * `years_remaining` - The number of milestone years still to run
+///
+/// # Returns
+///
+/// The estimated time remaining, based on the mean duration of the years run so far, or `None` if
+/// no years have been run yet.
+fn estimate_time_remaining(
+    year_durations: &[Duration],
+    years_remaining: usize,
+) -> Option<Duration> {
+    if year_durations.is_empty() {
+        return None;
+    }
+
+    let mean = year_durations.iter().sum::<Duration>() / year_durations.len() as u32;
+    Some(mean * years_remaining as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::load_model;
+    use crate::settings::Settings;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    /// Run the simple example model, writing output files to a temporary folder
+    #[test]
+    fn test_run_simple_example() {
+        let model_dir = Path::new(file!())
+            .parent()
+            .unwrap()
+            .parent()
+            .unwrap()
+            .join("examples")
+            .join("simple");
+        let settings = Settings::from_path(&model_dir).unwrap();
+        let (model, mut assets) = load_model(&model_dir, &settings).unwrap();
+
+        let dir = tempdir().unwrap();
+        let mut writer = DataWriter::create(dir.path()).unwrap();
+        run(&model, &mut assets, &mut writer).unwrap();
+
+        // All assets are commissioned in 2020 and have been decommissioned by 2100
+        assert_eq!(
+            fs::read_to_string(dir.path().join("capacity.csv")).unwrap(),
+            "milestone_year,region_id,process_id,agent_id,capacity\n\
+             2020,GBR,GASCGT,A0_ELC,2.43\n\
+             2020,GBR,GASDRV,A0_GEX,4002.26\n\
+             2020,GBR,GASPRC,A0_GPR,3782.13\n\
+             2020,GBR,RELCHP,A0_RES,399.98\n\
+             2020,GBR,RGASBR,A0_RES,2900.0\n\
+             2020,GBR,WNDFRM,A0_ELC,3.964844\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.path().join("decommissions.csv")).unwrap(),
+            "milestone_year,agent_id,process_id,region_id,capacity,commission_year,\
+             decommission_year\n\
+             2100,A0_GEX,GASDRV,GBR,4002.26,2020,2045\n\
+             2100,A0_GPR,GASPRC,GBR,3782.13,2020,2045\n\
+             2100,A0_ELC,WNDFRM,GBR,3.964844,2020,2045\n\
+             2100,A0_ELC,GASCGT,GBR,2.43,2020,2050\n\
+             2100,A0_RES,RGASBR,GBR,2900.0,2020,2035\n\
+             2100,A0_RES,RELCHP,GBR,399.98,2020,2035\n"
+        );
+    }
+
+    #[test]
+    fn test_estimate_time_remaining() {
+        assert!(estimate_time_remaining(&[], 3).is_none());
+
+        let durations = [Duration::from_secs(2), Duration::from_secs(4)];
+        assert_eq!(
+            estimate_time_remaining(&durations, 3),
+            Some(Duration::from_secs(9))
+        );
+        assert_eq!(estimate_time_remaining(&durations, 0), Some(Duration::ZERO));
     }
 }
@@ -4,7 +4,7 @@
 //! day and time of year.
 #![allow(missing_docs)]
 use crate::input::*;
-use anyhow::{Context, Result};
+use anyhow::{ensure, Context, Result};
 use itertools::Itertools;
 use serde_string_enum::DeserializeLabeledStringEnum;
 use std::collections::{HashMap, HashSet};
@@ -28,7 +28,7 @@ impl Display for TimeSliceID {
 }
 
 /// Represents a time slice read from an input file, which can be all
-#[derive(PartialEq, Clone, Debug)]
+#[derive(PartialEq, Eq, Hash, Clone, Debug)]
 pub enum TimeSliceSelection {
     /// All year and all day
     Annual,
@@ -38,6 +38,17 @@ pub enum TimeSliceSelection {
     Single(TimeSliceID),
 }
 
+impl TimeSliceSelection {
+    /// Whether the specified time slice is covered by this selection
+    pub fn contains(&self, time_slice: &TimeSliceID) -> bool {
+        match self {
+            Self::Annual => true,
+            Self::Season(season) => time_slice.season == *season,
+            Self::Single(selected) => time_slice == selected,
+        }
+    }
+}
+
 /// Information about the time slices in the simulation, including names and fractions
 #[derive(PartialEq, Debug)]
 pub struct TimeSliceInfo {
@@ -67,6 +78,45 @@ impl Default for TimeSliceInfo {
 }
 
 impl TimeSliceInfo {
+    /// Check that the time slice information is internally consistent.
+    ///
+    /// The fractions must sum to one, every time slice must refer to a declared season and time of
+    /// day and every declared season and time of day must be used by at least one time slice.
+    pub fn validate(&self) -> Result<()> {
+        check_fractions_sum_to_one(self.fractions.values().copied())
+            .context("Invalid time slice fractions")?;
+
+        for time_slice in self.fractions.keys() {
+            ensure!(
+                self.seasons.contains(&time_slice.season),
+                "Time slice {time_slice} refers to undeclared season {}",
+                time_slice.season
+            );
+            ensure!(
+                self.times_of_day.contains(&time_slice.time_of_day),
+                "Time slice {time_slice} refers to undeclared time of day {}",
+                time_slice.time_of_day
+            );
+        }
+
+        for season in self.seasons.iter().sorted() {
+            ensure!(
+                self.fractions.keys().any(|ts| ts.season == *season),
+                "Season {season} has no time slices"
+            );
+        }
+        for time_of_day in self.times_of_day.iter().sorted() {
+            ensure!(
+                self.fractions
+                    .keys()
+                    .any(|ts| ts.time_of_day == *time_of_day),
+                "Time of day {time_of_day} has no time slices"
+            );
+        }
+
+        Ok(())
+    }
+
     /// Get the `TimeSliceID` corresponding to the `time_slice`.
     ///
     /// `time_slice` must be in the form "season.time_of_day".
@@ -191,10 +241,60 @@ impl TimeSliceInfo {
         self.iterate_selection_share(selection)
             .map(move |(ts, share)| (ts, value * share))
     }
+
+    /// Iterate over all the [`TimeSliceSelection`]s at the specified level.
+    ///
+    /// For example, at the [`TimeSliceLevel::Season`] level, there will be one selection per
+    /// season.
+    pub fn iter_selections_at_level(
+        &self,
+        level: TimeSliceLevel,
+    ) -> Box<dyn Iterator<Item = TimeSliceSelection> + '_> {
+        match level {
+            TimeSliceLevel::Annual => Box::new(iter::once(TimeSliceSelection::Annual)),
+            TimeSliceLevel::Season => Box::new(
+                self.seasons
+                    .iter()
+                    .map(|season| TimeSliceSelection::Season(Rc::clone(season))),
+            ),
+            TimeSliceLevel::DayNight => {
+                Box::new(self.iter_ids().cloned().map(TimeSliceSelection::Single))
+            }
+        }
+    }
+
+    /// Aggregate the time slices to the specified level.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - The level to aggregate to
+    ///
+    /// # Returns
+    ///
+    /// A map of the selections at `level` to the fraction of the year they cover, or an error if
+    /// these fractions do not sum to one.
+    pub fn aggregate_to_level(
+        &self,
+        level: TimeSliceLevel,
+    ) -> Result<HashMap<TimeSliceSelection, f64>> {
+        let aggregated: HashMap<_, _> = self
+            .iter_selections_at_level(level)
+            .map(|selection| {
+                let fraction = self
+                    .iter_selection(&selection)
+                    .map(|(_, fraction)| fraction)
+                    .sum();
+                (selection, fraction)
+            })
+            .collect();
+        check_fractions_sum_to_one(aggregated.values().copied())?;
+
+        Ok(aggregated)
+    }
 }
 
 /// Refers to a particular aspect of a time slice
-#[derive(PartialEq, Debug, DeserializeLabeledStringEnum)]
+#[derive(PartialEq, Clone, Copy, Debug, DeserializeLabeledStringEnum)]
 pub enum TimeSliceLevel {
     #[string = "annual"]
     Annual,
@@ -209,6 +309,58 @@ mod tests {
     use super::*;
     use float_cmp::assert_approx_eq;
 
+    #[test]
+    fn test_time_slice_info_validate() {
+        let slices = [
+            ("winter", "day", 0.25),
+            ("winter", "night", 0.25),
+            ("summer", "day", 0.5),
+        ]
+        .map(|(season, time_of_day, fraction)| {
+            (
+                TimeSliceID {
+                    season: season.into(),
+                    time_of_day: time_of_day.into(),
+                },
+                fraction,
+            )
+        });
+        let create_info = || TimeSliceInfo {
+            seasons: ["winter".into(), "summer".into()].into_iter().collect(),
+            times_of_day: ["day".into(), "night".into()].into_iter().collect(),
+            fractions: slices.iter().cloned().collect(),
+        };
+
+        // Valid
+        assert!(create_info().validate().is_ok());
+        assert!(TimeSliceInfo::default().validate().is_ok());
+
+        // Fractions don't sum to one
+        let mut info = create_info();
+        *info.fractions.get_mut(&slices[2].0).unwrap() = 0.4;
+        assert!(info.validate().is_err());
+
+        // Undeclared season
+        let mut info = create_info();
+        info.seasons.remove("summer");
+        assert!(info.validate().is_err());
+
+        // Undeclared time of day
+        let mut info = create_info();
+        info.times_of_day.remove("night");
+        assert!(info.validate().is_err());
+
+        // Declared season with no time slices
+        let mut info = create_info();
+        info.seasons.insert("autumn".into());
+        assert!(info.validate().is_err());
+
+        // Declared time of day with no time slices
+        let mut info = create_info();
+        info.times_of_day.insert("evening".into());
+        assert!(info.validate().is_err());
+    }
+
     #[test]
     fn test_iter_selection() {
         let slices = [
@@ -252,6 +404,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_time_slice_selection_contains() {
+        let winter_day = TimeSliceID {
+            season: "winter".into(),
+            time_of_day: "day".into(),
+        };
+        let winter_night = TimeSliceID {
+            season: "winter".into(),
+            time_of_day: "night".into(),
+        };
+        let summer_day = TimeSliceID {
+            season: "summer".into(),
+            time_of_day: "day".into(),
+        };
+
+        // Annual
+        assert!(TimeSliceSelection::Annual.contains(&winter_day));
+        assert!(TimeSliceSelection::Annual.contains(&summer_day));
+
+        // Season
+        let selection = TimeSliceSelection::Season("winter".into());
+        assert!(selection.contains(&winter_day));
+        assert!(selection.contains(&winter_night));
+        assert!(!selection.contains(&summer_day));
+
+        // Single
+        let selection = TimeSliceSelection::Single(winter_day.clone());
+        assert!(selection.contains(&winter_day));
+        assert!(!selection.contains(&winter_night));
+    }
+
     #[test]
     fn test_calculate_share() {
         let slices = [
@@ -312,4 +495,59 @@ mod tests {
         let expected: HashMap<_, _> = HashMap::from_iter(iter::once((time_slice, 8.0)));
         check_share!(selection, expected);
     }
+
+    #[test]
+    fn test_aggregate_to_level() {
+        let fractions = [
+            ("winter", "day", 0.1),
+            ("winter", "night", 0.3),
+            ("summer", "day", 0.4),
+            ("summer", "night", 0.2),
+        ];
+        let ts_info = TimeSliceInfo {
+            seasons: ["winter".into(), "summer".into()].into_iter().collect(),
+            times_of_day: ["day".into(), "night".into()].into_iter().collect(),
+            fractions: fractions
+                .iter()
+                .map(|(season, time_of_day, fraction)| {
+                    let ts = TimeSliceID {
+                        season: (*season).into(),
+                        time_of_day: (*time_of_day).into(),
+                    };
+                    (ts, *fraction)
+                })
+                .collect(),
+        };
+
+        macro_rules! check_aggregate {
+            ($level:expr, $expected:expr) => {
+                let expected: HashMap<TimeSliceSelection, f64> = $expected.into_iter().collect();
+                let actual = ts_info.aggregate_to_level($level).unwrap();
+                assert_eq!(actual.len(), expected.len());
+                for (selection, fraction) in actual {
+                    assert_approx_eq!(f64, fraction, *expected.get(&selection).unwrap());
+                }
+            };
+        }
+
+        // Annual
+        check_aggregate!(TimeSliceLevel::Annual, [(TimeSliceSelection::Annual, 1.0)]);
+
+        // Season
+        check_aggregate!(
+            TimeSliceLevel::Season,
+            [
+                (TimeSliceSelection::Season("winter".into()), 0.4),
+                (TimeSliceSelection::Season("summer".into()), 0.6),
+            ]
+        );
+
+        // Day/night
+        check_aggregate!(
+            TimeSliceLevel::DayNight,
+            ts_info
+                .iter()
+                .map(|(ts, fraction)| (TimeSliceSelection::Single(ts.clone()), fraction))
+        );
+    }
 }
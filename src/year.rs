@@ -0,0 +1,45 @@
+//! Code for working with years.
+
+/// Calculate the factor by which a value in a future year is discounted back to the base year.
+///
+/// # Arguments
+///
+/// * `rate` - The discount rate
+/// * `years_from_base` - The number of years after the base year
+///
+/// # Returns
+///
+/// The discount factor, i.e. `1 / (1 + rate)^years_from_base`.
+pub fn discount_factor(rate: f64, years_from_base: u32) -> f64 {
+    1.0 / (1.0 + rate).powi(years_from_base as i32)
+}
+
+/// Get the number of years from `base` to `target`.
+///
+/// # Returns
+///
+/// The number of years, or `None` if `target` is before `base`.
+pub fn years_between(base: u32, target: u32) -> Option<u32> {
+    target.checked_sub(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use float_cmp::assert_approx_eq;
+
+    #[test]
+    fn test_discount_factor() {
+        assert_approx_eq!(f64, discount_factor(0.1, 0), 1.0);
+        assert_approx_eq!(f64, discount_factor(0.1, 1), 1.0 / 1.1);
+        assert_approx_eq!(f64, discount_factor(0.05, 2), 1.0 / 1.1025);
+        assert_approx_eq!(f64, discount_factor(0.0, 10), 1.0);
+    }
+
+    #[test]
+    fn test_years_between() {
+        assert_eq!(years_between(2020, 2030), Some(10));
+        assert_eq!(years_between(2020, 2020), Some(0));
+        assert_eq!(years_between(2030, 2020), None);
+    }
+}
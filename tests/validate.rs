@@ -0,0 +1,18 @@
+//! An integration test for the `validate` command.
+//!
+//! This is kept separate from the unit tests as the command initialises the global logger.
+use muse2::commands::handle_validate_command;
+use std::path::Path;
+use tempfile::tempdir;
+
+#[test]
+fn test_handle_validate_command() {
+    let model_dir = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("examples")
+        .join("simple");
+    handle_validate_command(&model_dir).unwrap();
+
+    // A directory without model files is invalid
+    let dir = tempdir().unwrap();
+    assert!(handle_validate_command(&dir.path().to_path_buf()).is_err());
+}